@@ -0,0 +1,107 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use scheduler::scheduler::config::{Config, SchedulingPolicy};
+use scheduler::scheduler::Scheduler;
+
+/// Operations timed per benchmark, spread over as many rounds as needed
+const OPERATIONS: usize = 400_000;
+
+fn scheduler_with(processes: usize) -> Scheduler {
+    Scheduler::with_config(Config {
+        processes,
+        ..Config::default()
+    })
+}
+
+fn bench(name: &str, operations: usize, mut run: impl FnMut()) {
+    let rounds = (OPERATIONS / operations).max(1);
+
+    let start = Instant::now();
+    for _ in 0..rounds {
+        run();
+    }
+    let elapsed = start.elapsed();
+
+    #[allow(clippy::cast_precision_loss)]
+    let per_operation = elapsed.as_nanos() as f64 / (rounds * operations) as f64;
+    println!("{name:<40} {per_operation:>10.1} ns/op");
+}
+
+/// Fills the table with children of process 1, then tears it down
+fn create_destroy(processes: usize) {
+    let mut scheduler = scheduler_with(processes);
+
+    bench(&format!("create_destroy/{processes}"), processes, || {
+        scheduler.init();
+        scheduler.create(2);
+        for i in 0..processes - 2 {
+            black_box(scheduler.create(i32::try_from(i % 2).unwrap_or(0)));
+        }
+        black_box(scheduler.destroy(1));
+    });
+}
+
+/// Round robin through a full top priority level
+fn timeout(processes: usize) {
+    let mut scheduler = scheduler_with(processes);
+    for _ in 1..processes {
+        scheduler.create(2);
+    }
+
+    bench(&format!("timeout/{processes}"), processes, || {
+        for _ in 0..processes {
+            black_box(scheduler.timeout());
+        }
+    });
+}
+
+/// Round robin by virtual runtime through a full table
+fn cfs_timeout(processes: usize) {
+    let mut scheduler = Scheduler::with_config(Config {
+        processes,
+        policy: SchedulingPolicy::CompletelyFair,
+        ..Config::default()
+    });
+    for _ in 1..processes {
+        scheduler.create(2);
+    }
+
+    bench(&format!("cfs_timeout/{processes}"), processes, || {
+        for _ in 0..processes {
+            black_box(scheduler.timeout());
+        }
+    });
+}
+
+/// Every process queues on resource 0, then the unit is passed down the waitlist
+fn request_release(processes: usize) {
+    let mut scheduler = scheduler_with(processes);
+
+    bench(&format!("request_release/{processes}"), processes, || {
+        scheduler.init();
+        for _ in 1..processes {
+            scheduler.create(2);
+        }
+        black_box(scheduler.request(0, 1));
+        scheduler.timeout();
+        for _ in 2..processes {
+            black_box(scheduler.request(0, 1));
+        }
+
+        // Each Holder Hands The Unit To The Next Waiter
+        for _ in 1..processes {
+            black_box(scheduler.release(0, 1));
+            scheduler.timeout();
+        }
+    });
+}
+
+fn main() {
+    for processes in [16, 256, 4096] {
+        create_destroy(processes);
+        timeout(processes);
+        cfs_timeout(processes);
+        request_release(processes);
+    }
+}
@@ -1,12 +1,123 @@
 use std::process::ExitCode;
 
-use scheduler::process::interactive_shell;
+use scheduler::output::OutputFormat;
+use scheduler::process::{interactive_shell_with, ShellOptions};
+use scheduler::scheduler::config::{
+    BalanceStrategy, ExitPolicy, QueueMode, RecoveryStrategy, ResourceConfig, SchedulingPolicy,
+};
+
+/// Value of a flag that takes a count
+fn count(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<usize, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("{flag} needs a number"))?;
+
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {flag} value {value}"))
+}
+
+/// Value of a flag that names a setting, parsed with the setting's own `parse`
+fn setting<T>(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+    choices: &str,
+    parse: fn(&str) -> Option<T>,
+) -> Result<T, String> {
+    let name = args
+        .next()
+        .ok_or_else(|| format!("{flag} needs {choices}"))?;
+
+    parse(&name).ok_or_else(|| format!("Unknown {flag} value {name}, expected {choices}"))
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<ShellOptions, String> {
+    let mut options = ShellOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let name = args.next().ok_or("--format needs plain, json or verbose")?;
+                options.format =
+                    OutputFormat::parse(&name).ok_or_else(|| format!("Unknown format {name}"))?;
+            }
+            "--jobs" => options.jobs = count(&mut args, "--jobs")?,
+            "--resources" => {
+                let list = args
+                    .next()
+                    .ok_or("--resources needs a list such as disk=2,printer=1")?;
+                options.config.resources = ResourceConfig::parse_list(&list)?;
+            }
+            "--recovery" => {
+                let name = args
+                    .next()
+                    .ok_or("--recovery needs rollback or terminate")?;
+                options.config.deadlock_recovery = Some(
+                    RecoveryStrategy::parse(&name)
+                        .ok_or_else(|| format!("Unknown recovery strategy {name}"))?,
+                );
+            }
+            "--policy" => {
+                options.config.policy = setting(
+                    &mut args,
+                    "--policy",
+                    "priority, edf, rate-monotonic, cfs, sjf or srtf",
+                    SchedulingPolicy::parse,
+                )?;
+            }
+            "--cores" => options.config.cores = count(&mut args, "--cores")?,
+            "--queues" => {
+                options.config.queue_mode = setting(
+                    &mut args,
+                    "--queues",
+                    "shared or per-core",
+                    QueueMode::parse,
+                )?;
+            }
+            "--balancing" => {
+                options.config.balancing = setting(
+                    &mut args,
+                    "--balancing",
+                    "disabled, push[:period] or stealing",
+                    BalanceStrategy::parse,
+                )?;
+            }
+            "--exit-policy" => {
+                options.config.exit_policy = setting(
+                    &mut args,
+                    "--exit-policy",
+                    "cascade, reparent-to-init or reparent-to-grandparent",
+                    ExitPolicy::parse,
+                )?;
+            }
+            "--mailbox-capacity" => {
+                options.config.mailbox_capacity = Some(count(&mut args, "--mailbox-capacity")?);
+            }
+            "--initial-burst" => {
+                options.config.initial_burst = count(&mut args, "--initial-burst")?;
+            }
+            "--burst-weight" => options.config.burst_weight = count(&mut args, "--burst-weight")?,
+            "--processes" => options.config.processes = count(&mut args, "--processes")?,
+            _ => return Err(format!("Unknown argument {arg}")),
+        }
+    }
+
+    Ok(options)
+}
 
 fn main() -> ExitCode {
-    match interactive_shell("input.txt", "output.txt") {
-        Ok(_) => ExitCode::SUCCESS,
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match interactive_shell_with("input.txt", "output.txt", &options) {
+        Ok(()) => ExitCode::SUCCESS,
         Err(message) => {
-            eprintln!("{}", message);
+            eprintln!("{message}");
             ExitCode::FAILURE
         }
     }
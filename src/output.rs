@@ -0,0 +1,402 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::scheduler::pcb::PCBState;
+use crate::scheduler::realtime::DeadlineMiss;
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Running PIDs separated by spaces, `-1` for errors
+    #[default]
+    Plain,
+    /// One object per command with its opcode, arguments, result and running PIDs
+    Json,
+    /// Each command followed by the state changes it caused
+    Verbose,
+}
+
+impl OutputFormat {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(Self::Plain),
+            "json" => Some(Self::Json),
+            "verbose" => Some(Self::Verbose),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The scheduler refused the command without giving a reason, like for a negative ID
+    Rejected,
+    /// An ID is out of bounds or names nothing live
+    UnknownId,
+    /// The running process may not act on the target, or process 0 may not do it at all
+    NotPermitted,
+    InvalidArgument,
+    /// The command doesn't fit the current state
+    InvalidState,
+    UnknownCommand,
+    /// No live process or resource has the name
+    UnknownName,
+    UndefinedVariable,
+    InvalidAssignment,
+    FailedExpectation,
+}
+
+impl ErrorKind {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Rejected => "rejected",
+            Self::UnknownId => "unknown_id",
+            Self::NotPermitted => "not_permitted",
+            Self::InvalidArgument => "invalid_argument",
+            Self::InvalidState => "invalid_state",
+            Self::UnknownCommand => "unknown_command",
+            Self::UnknownName => "unknown_name",
+            Self::UndefinedVariable => "undefined_variable",
+            Self::InvalidAssignment => "invalid_assignment",
+            Self::FailedExpectation => "failed_expectation",
+        }
+    }
+}
+
+impl From<Refusal> for ErrorKind {
+    fn from(refusal: Refusal) -> Self {
+        match refusal {
+            Refusal::UnknownId => Self::UnknownId,
+            Refusal::NotPermitted => Self::NotPermitted,
+            Refusal::InvalidArgument => Self::InvalidArgument,
+            Refusal::InvalidState => Self::InvalidState,
+        }
+    }
+}
+
+/// Outcome of a single command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub line: usize,
+    pub opcode: String,
+    pub arguments: Vec<String>,
+    pub error: Option<ErrorKind>,
+    /// Running PID of every core after the command
+    pub running: Vec<usize>,
+    /// Name of the process running on every core
+    pub names: Vec<Option<String>>,
+    /// Lines the command prints, like the `ps` table, written out in batch order
+    pub messages: Vec<String>,
+    /// Only collected for the verbose format
+    pub changes: Vec<String>,
+}
+
+/// Scheduler state compared before and after a command in the verbose format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    states: Vec<Option<PCBState>>,
+    names: Vec<Option<String>>,
+    running: Vec<usize>,
+    available: Vec<usize>,
+    resource_names: Vec<Option<String>>,
+    deadline_misses: Vec<DeadlineMiss>,
+}
+
+impl Snapshot {
+    #[must_use]
+    pub fn new(scheduler: &Scheduler) -> Self {
+        Self {
+            states: scheduler
+                .processes()
+                .map(|pcb| pcb.map(|pcb| pcb.state))
+                .collect(),
+            names: scheduler
+                .processes()
+                .map(|pcb| pcb.and_then(|pcb| pcb.name.clone()))
+                .collect(),
+            running: (0..scheduler.cores())
+                .filter_map(|core| scheduler.running(core))
+                .collect(),
+            available: scheduler
+                .resources()
+                .map(|rcb| rcb.units_available)
+                .collect(),
+            resource_names: scheduler.resources().map(|rcb| rcb.name.clone()).collect(),
+            deadline_misses: scheduler.deadline_misses().to_vec(),
+        }
+    }
+
+    /// Describes what changed since `self`, one line per change
+    #[must_use]
+    pub fn changes(&self, after: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        for (pid, (old, new)) in self.states.iter().zip(&after.states).enumerate() {
+            match (old, new) {
+                (None, Some(state)) => changes.push(format!(
+                    "process {} created {state:?}",
+                    label(pid, after.names[pid].as_ref())
+                )),
+                (Some(_), None) => {
+                    changes.push(format!(
+                        "process {} removed",
+                        label(pid, self.names[pid].as_ref())
+                    ));
+                }
+                (Some(old), Some(new)) if old != new => changes.push(format!(
+                    "process {} {old:?} -> {new:?}",
+                    label(pid, after.names[pid].as_ref())
+                )),
+                _ => {}
+            }
+        }
+
+        if self.running != after.running {
+            changes.push(format!(
+                "running {} -> {}",
+                labels(&self.running, &self.names),
+                labels(&after.running, &after.names)
+            ));
+        }
+
+        for (rid, (old, new)) in self.available.iter().zip(&after.available).enumerate() {
+            if old != new {
+                changes.push(format!(
+                    "resource {} available {old} -> {new}",
+                    label(rid, self.resource_names[rid].as_ref())
+                ));
+            }
+        }
+
+        for (rid, available) in after
+            .available
+            .iter()
+            .enumerate()
+            .skip(self.available.len())
+        {
+            changes.push(format!(
+                "resource {} created available {available}",
+                label(rid, after.resource_names[rid].as_ref())
+            ));
+        }
+
+        for miss in after
+            .deadline_misses
+            .iter()
+            .skip(self.deadline_misses.len())
+        {
+            changes.push(format!(
+                "process {} missed deadline {}",
+                label(miss.pid, after.names[miss.pid].as_ref()),
+                miss.deadline
+            ));
+        }
+
+        changes
+    }
+}
+
+fn join(values: &[usize], separator: &str) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// `1(logger)` for a named process or resource, just the ID otherwise
+fn label(id: usize, name: Option<&String>) -> String {
+    name.map_or_else(|| id.to_string(), |name| format!("{id}({name})"))
+}
+
+/// Labels running PIDs with the names in `names`, which is indexed by PID
+fn labels(running: &[usize], names: &[Option<String>]) -> String {
+    running
+        .iter()
+        .map(|&pid| label(pid, names[pid].as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => {
+                write!(escaped, "\\u{:04x}", u32::from(c))
+                    .expect("Writing to a String should not fail");
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn plain_batch(batch: &[Record]) -> String {
+    batch
+        .iter()
+        .map(|record| match record.error {
+            Some(_) => String::from("-1"),
+            None => join(&record.running, ","),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn json_batch(batch: &[Record]) -> String {
+    let commands: Vec<String> = batch
+        .iter()
+        .map(|record| {
+            let arguments: Vec<String> = record
+                .arguments
+                .iter()
+                .map(|argument| format!("\"{}\"", escape(argument)))
+                .collect();
+            let outcome = record.error.map_or_else(
+                || format!("\"result\": [{}]", join(&record.running, ", ")),
+                |error| format!("\"error\": \"{}\"", error.as_str()),
+            );
+
+            // Names Are Only Listed While A Named Process Runs
+            let names = if record.names.iter().any(Option::is_some) {
+                let names: Vec<String> = record
+                    .names
+                    .iter()
+                    .map(|name| {
+                        name.as_ref()
+                            .map_or_else(|| String::from("null"), |name| format!("\"{}\"", escape(name)))
+                    })
+                    .collect();
+                format!(", \"names\": [{}]", names.join(", "))
+            } else {
+                String::new()
+            };
+
+            let messages = if record.messages.is_empty() {
+                String::new()
+            } else {
+                let messages: Vec<String> = record
+                    .messages
+                    .iter()
+                    .map(|message| format!("\"{}\"", escape(message)))
+                    .collect();
+                format!(", \"messages\": [{}]", messages.join(", "))
+            };
+
+            format!(
+                "    {{\"line\": {}, \"opcode\": \"{}\", \"args\": [{}], {outcome}, \"running\": [{}]{names}{messages}}}",
+                record.line,
+                escape(&record.opcode),
+                arguments.join(", "),
+                join(&record.running, ", ")
+            )
+        })
+        .collect();
+
+    format!("  [\n{}\n  ]", commands.join(",\n"))
+}
+
+fn verbose_batch(number: usize, batch: &[Record]) -> String {
+    let mut text = format!("batch {number}\n");
+
+    for record in batch {
+        let command = std::iter::once(&record.opcode)
+            .chain(&record.arguments)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let outcome = record.error.map_or_else(
+            || {
+                record
+                    .running
+                    .iter()
+                    .zip(&record.names)
+                    .map(|(&pid, name)| label(pid, name.as_ref()))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            },
+            |error| format!("error ({})", error.as_str()),
+        );
+
+        writeln!(text, "  line {}: {command} => {outcome}", record.line)
+            .expect("Writing to a String should not fail");
+        for message in &record.messages {
+            writeln!(text, "    | {message}").expect("Writing to a String should not fail");
+        }
+        for change in &record.changes {
+            writeln!(text, "    {change}").expect("Writing to a String should not fail");
+        }
+    }
+
+    text
+}
+
+/// Writes batches as they complete, flushing after each one
+#[derive(Debug)]
+pub struct RecordWriter<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    batches: usize,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub const fn new(writer: W, format: OutputFormat) -> Self {
+        Self {
+            writer,
+            format,
+            batches: 0,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if writing fails
+    pub fn write_batch(&mut self, batch: &[Record]) -> io::Result<()> {
+        // Printed Text Follows The Batch Order Even When Batches Run In Parallel
+        for message in batch.iter().flat_map(|record| &record.messages) {
+            println!("{message}");
+        }
+
+        match self.format {
+            OutputFormat::Plain => {
+                if self.batches > 0 {
+                    write!(self.writer, "\r\n")?;
+                }
+                write!(self.writer, "{}", plain_batch(batch))?;
+            }
+            OutputFormat::Json => {
+                let separator = if self.batches == 0 { "[\n" } else { ",\n" };
+                write!(self.writer, "{separator}{}", json_batch(batch))?;
+            }
+            OutputFormat::Verbose => {
+                write!(self.writer, "{}", verbose_batch(self.batches + 1, batch))?;
+            }
+        }
+
+        self.batches += 1;
+        self.writer.flush()
+    }
+
+    /// Closes the output, which is still well formed if it stops early
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if writing fails
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.format {
+            // The Last Batch Ends With A Space Instead Of A Line Break
+            OutputFormat::Plain if self.batches > 0 => write!(self.writer, " ")?,
+            OutputFormat::Json if self.batches == 0 => writeln!(self.writer, "[]")?,
+            OutputFormat::Json => write!(self.writer, "\n]\n")?,
+            OutputFormat::Plain | OutputFormat::Verbose => {}
+        }
+
+        self.writer.flush()
+    }
+}
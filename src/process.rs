@@ -1,123 +1,714 @@
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufWriter, Write};
+use std::io::{self, BufRead, BufReader, BufWriter};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
+use std::str::FromStr;
+use std::thread;
 
+use crate::output::{ErrorKind, OutputFormat, Record, RecordWriter, Snapshot};
+use crate::scheduler::command::Command;
+use crate::scheduler::config::{Config, RecoveryStrategy};
+use crate::scheduler::pcb::CreateOptions;
 use crate::scheduler::Scheduler;
 
-fn read_file(filename: &str) -> io::Result<Vec<Vec<Vec<String>>>> {
-    let path = Path::new(filename);
-    let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
+#[derive(Debug, Clone)]
+struct Instruction {
+    /// Line in the input file, starting at 1
+    line: usize,
+    words: Vec<String>,
+}
+
+/// Lazily splits the input into blank-line-separated batches, so only one batch is in memory
+fn read_batches(reader: impl BufRead) -> impl Iterator<Item = io::Result<Vec<Instruction>>> {
+    let mut lines = reader.lines().enumerate();
+
+    std::iter::from_fn(move || {
+        let mut current_vector = Vec::new();
 
-    let mut instruction_vectors = Vec::new();
-    let mut current_vector = Vec::new();
+        for (index, line) in lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error)),
+            };
 
-    for line in reader.lines() {
-        let line: String = line?;
-        if line.trim().is_empty() {
-            if !current_vector.is_empty() {
-                instruction_vectors.push(current_vector);
-                current_vector = Vec::new();
+            if line.trim().is_empty() {
+                if !current_vector.is_empty() {
+                    return Some(expand(&current_vector));
+                }
+            } else {
+                // Everything After A '#' Is A Comment
+                let words: Vec<String> = line
+                    .split_whitespace()
+                    .take_while(|word| !word.starts_with('#'))
+                    .map(String::from)
+                    .collect();
+
+                if !words.is_empty() {
+                    current_vector.push(Instruction {
+                        line: index + 1,
+                        words,
+                    });
+                }
             }
-        } else {
-            let words = line.split_whitespace().map(String::from).collect();
-            current_vector.push(words);
         }
+
+        (!current_vector.is_empty()).then(|| expand(&current_vector))
+    })
+}
+
+/// Unrolls `repeat N ... end` blocks, which may be nested
+fn expand(instruction_vector: &[Instruction]) -> io::Result<Vec<Instruction>> {
+    let invalid = |line, message| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {line}: {message}"),
+        )
+    };
+
+    // Each Open Block Keeps Its Line, Repeat Count And Body
+    let mut blocks = vec![(0, 1, Vec::new())];
+
+    for instruction in instruction_vector {
+        match instruction.words[0].as_str() {
+            "repeat" => {
+                let count = instruction
+                    .words
+                    .get(1)
+                    .and_then(|count| count.parse().ok())
+                    .ok_or_else(|| {
+                        invalid(instruction.line, "repeat needs a non-negative count")
+                    })?;
+                blocks.push((instruction.line, count, Vec::new()));
+            }
+            "end" => {
+                if blocks.len() == 1 {
+                    return Err(invalid(instruction.line, "end without a matching repeat"));
+                }
+
+                let (_, count, body) = blocks.pop().expect("Blocks should not be empty");
+                let parent = &mut blocks.last_mut().expect("Blocks should not be empty").2;
+                for _ in 0..count {
+                    parent.extend(body.iter().cloned());
+                }
+            }
+            _ => blocks
+                .last_mut()
+                .expect("Blocks should not be empty")
+                .2
+                .push(instruction.clone()),
+        }
+    }
+
+    let (line, _, body) = blocks.pop().expect("Blocks should not be empty");
+    if !blocks.is_empty() {
+        return Err(invalid(line, "repeat without a matching end"));
+    }
+
+    Ok(body)
+}
+
+/// Replaces `$name` words with the PID stored in the variable
+fn substitute(instruction: &[String], variables: &HashMap<String, usize>) -> Option<Vec<String>> {
+    instruction
+        .iter()
+        .map(|word| {
+            word.strip_prefix('$').map_or_else(
+                || Some(word.clone()),
+                |name| variables.get(name).map(ToString::to_string),
+            )
+        })
+        .collect()
+}
+
+fn option<'a>(instruction: &'a [String], key: &str) -> Option<&'a str> {
+    instruction
+        .iter()
+        .skip(2)
+        .position(|word| word == key)
+        .and_then(|pos| instruction.get(pos + 3))
+        .map(String::as_str)
+}
+
+/// Resolves a PID given as a number or as the name of a live process
+fn pid(scheduler: &Scheduler, word: &str) -> Result<i32, ErrorKind> {
+    word.parse().or_else(|_| {
+        scheduler
+            .pid_of(word)
+            .and_then(|pid| i32::try_from(pid).ok())
+            .ok_or_else(|| {
+                eprintln!("SHELL: Unknown Process {word}");
+                ErrorKind::UnknownName
+            })
+    })
+}
+
+/// Resolves a RID given as a number or as the name of a resource
+fn rid(scheduler: &Scheduler, word: &str) -> Result<i32, ErrorKind> {
+    word.parse().or_else(|_| {
+        scheduler
+            .rid_of(word)
+            .and_then(|rid| i32::try_from(rid).ok())
+            .ok_or_else(|| {
+                eprintln!("SHELL: Unknown Resource {word}");
+                ErrorKind::UnknownName
+            })
+    })
+}
+
+/// Checks `expect running|state|avail|waitlist|failed|collected|deadlocked|group|missed ...` against the live scheduler state
+fn check_expectation(scheduler: &Scheduler, arguments: &[String]) -> Result<(), String> {
+    let argument = |index: usize| {
+        arguments
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| String::from("Missing Argument"))
+    };
+    // Processes And Resources May Be Given By Name
+    let id = |index: usize, lookup: fn(&Scheduler, &str) -> Option<usize>| {
+        let word = argument(index)?;
+        word.parse::<usize>()
+            .ok()
+            .or_else(|| lookup(scheduler, word))
+            .ok_or_else(|| format!("Invalid Argument {word}"))
+    };
+
+    let (expected, actual) = match argument(0)? {
+        "running" => (
+            id(1, Scheduler::pid_of)?.to_string(),
+            scheduler.running_pid.to_string(),
+        ),
+        "state" => (
+            argument(2)?.to_uppercase(),
+            scheduler
+                .process(id(1, Scheduler::pid_of)?)
+                .map_or_else(|| String::from("NONE"), |pcb| format!("{:?}", pcb.state)),
+        ),
+        "avail" => (
+            argument(2)?.to_string(),
+            scheduler.resource(id(1, Scheduler::rid_of)?).map_or_else(
+                || String::from("NONE"),
+                |rcb| rcb.units_available.to_string(),
+            ),
+        ),
+        "waitlist" => (
+            // `[1, 2]` Splits Into Several Words
+            arguments.get(2..).unwrap_or_default().concat(),
+            scheduler.waiters(id(1, Scheduler::rid_of)?).map_or_else(
+                || String::from("NONE"),
+                |waitlist| {
+                    let pids: Vec<String> = waitlist.iter().map(|x| x.pid.to_string()).collect();
+                    format!("[{}]", pids.join(","))
+                },
+            ),
+        ),
+        "deadlocked" => (
+            arguments.get(1..).unwrap_or_default().concat(),
+            format!(
+                "[{}]",
+                scheduler
+                    .deadlocked()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        ),
+        "failed" => (
+            if argument(2)?.eq_ignore_ascii_case("none") {
+                String::from("NONE")
+            } else {
+                id(2, Scheduler::rid_of)?.to_string()
+            },
+            scheduler
+                .process(id(1, Scheduler::pid_of)?)
+                .and_then(|pcb| pcb.failed_request)
+                .map_or_else(|| String::from("NONE"), |rid| rid.to_string()),
+        ),
+        "collected" => (
+            if argument(2)?.eq_ignore_ascii_case("none") {
+                String::from("NONE")
+            } else {
+                format!("{} {}", id(2, Scheduler::pid_of)?, argument(3)?)
+            },
+            scheduler
+                .process(id(1, Scheduler::pid_of)?)
+                .and_then(|pcb| pcb.collected)
+                .map_or_else(
+                    || String::from("NONE"),
+                    |status| format!("{} {}", status.pid, status.code),
+                ),
+        ),
+        "group" => (
+            argument(2)?.to_string(),
+            scheduler
+                .process(id(1, Scheduler::pid_of)?)
+                .map_or_else(|| String::from("NONE"), |pcb| pcb.group.to_string()),
+        ),
+        "missed" => {
+            let pid = id(1, Scheduler::pid_of)?;
+            let missed = scheduler
+                .deadline_misses()
+                .iter()
+                .filter(|miss| miss.pid == pid);
+            (argument(2)?.to_string(), missed.count().to_string())
+        }
+        other => return Err(format!("Unknown Expectation {other}")),
+    };
+
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("Expected {expected}, Found {actual}"))
     }
+}
+
+/// The word at `index`, a missing argument fails the command rather than the run
+fn word(instruction: &[String], index: usize) -> Result<&str, ErrorKind> {
+    instruction.get(index).map(String::as_str).ok_or_else(|| {
+        eprintln!("SHELL: Missing Argument");
+        ErrorKind::InvalidArgument
+    })
+}
+
+/// Parses a numeric argument, a malformed one fails the command rather than the run
+fn number<T: FromStr>(word: &str) -> Result<T, ErrorKind> {
+    word.parse().map_err(|_| {
+        eprintln!("SHELL: Invalid Argument {word}");
+        ErrorKind::InvalidArgument
+    })
+}
+
+/// Turns the words of a scheduler command into a `Command`, resolving names to IDs
+fn parse_command(scheduler: &Scheduler, instruction: &[String]) -> Result<Command, ErrorKind> {
+    let command = match instruction[0].as_str() {
+        "in" => Command::Init,
+        "cr" => Command::Create {
+            priority: number(word(instruction, 1)?)?,
+            options: CreateOptions {
+                name: option(instruction, "as").map(String::from),
+                affinity: option(instruction, "on").map(number).transpose()?,
+                burst: option(instruction, "burst").map(number).transpose()?,
+            },
+        },
+        "de" => Command::Destroy(pid(scheduler, word(instruction, 1)?)?),
+        "rq" => Command::Request {
+            rid: rid(scheduler, word(instruction, 1)?)?,
+            units: number(word(instruction, 2)?)?,
+            timeout: option(instruction, "timeout").map(number).transpose()?,
+        },
+        "tryrq" => Command::Request {
+            rid: rid(scheduler, word(instruction, 1)?)?,
+            units: number(word(instruction, 2)?)?,
+            timeout: Some(0),
+        },
+        "rl" => Command::Release {
+            rid: rid(scheduler, word(instruction, 1)?)?,
+            units: number(word(instruction, 2)?)?,
+        },
+        "to" => match instruction.get(1) {
+            Some(core) => Command::TimeoutOn(number(core)?),
+            None => Command::Timeout,
+        },
+        "rt" => Command::CreatePeriodic {
+            period: number(word(instruction, 1)?)?,
+            execution: number(word(instruction, 2)?)?,
+            deadline: instruction
+                .get(3)
+                .map(|deadline| number(deadline))
+                .transpose()?,
+        },
+        "cpu" => Command::SwitchCpu(number(word(instruction, 1)?)?),
+        "sp" => Command::Suspend(pid(scheduler, word(instruction, 1)?)?),
+        "rs" => Command::Resume(pid(scheduler, word(instruction, 1)?)?),
+        "ex" => Command::Exit(instruction.get(1).map_or(Ok(0), |code| number(code))?),
+        "wt" => Command::Wait(pid(scheduler, word(instruction, 1)?)?),
+        "lk" => Command::Lock(number(word(instruction, 1)?)?),
+        "ul" => Command::Unlock(number(word(instruction, 1)?)?),
+        "sm" => Command::SetSemaphore {
+            sid: number(word(instruction, 1)?)?,
+            value: number(word(instruction, 2)?)?,
+        },
+        "p" => Command::SemaphoreWait(number(word(instruction, 1)?)?),
+        "v" => Command::SemaphoreSignal(number(word(instruction, 1)?)?),
+        "sd" => Command::Send {
+            pid: pid(scheduler, word(instruction, 1)?)?,
+            value: number(word(instruction, 2)?)?,
+        },
+        "rv" => Command::Receive,
+        "io" => Command::Io {
+            dev: number(word(instruction, 1)?)?,
+            duration: number(word(instruction, 2)?)?,
+        },
+        "mkres" => Command::MakeResource {
+            inventory: number(word(instruction, 1)?)?,
+            name: option(instruction, "as").map(String::from),
+        },
+        "addunits" => Command::AddUnits {
+            rid: rid(scheduler, word(instruction, 1)?)?,
+            units: number(word(instruction, 2)?)?,
+        },
+        "rmunits" => Command::RemoveUnits {
+            rid: rid(scheduler, word(instruction, 1)?)?,
+            units: number(word(instruction, 2)?)?,
+            preempt: instruction.get(3).is_some_and(|word| word == "preempt"),
+        },
+        "setgrp" => Command::SetGroup {
+            pid: pid(scheduler, word(instruction, 1)?)?,
+            group: number(word(instruction, 2)?)?,
+        },
+        "deg" => Command::DestroyGroup(number(word(instruction, 1)?)?),
+        "spg" => Command::SuspendGroup(number(word(instruction, 1)?)?),
+        "rsg" => Command::ResumeGroup(number(word(instruction, 1)?)?),
+        "prg" => Command::SetGroupPriority {
+            group: number(word(instruction, 1)?)?,
+            priority: number(word(instruction, 2)?)?,
+        },
+        _ => return Err(ErrorKind::UnknownCommand),
+    };
+
+    Ok(command)
+}
 
-    if !current_vector.is_empty() {
-        instruction_vectors.push(current_vector);
+/// Runs a single command, returning the running PID. Text it prints is added to `messages`.
+fn execute(
+    scheduler: &mut Scheduler,
+    instruction: &[String],
+    messages: &mut Vec<String>,
+) -> Result<usize, ErrorKind> {
+    let result = match instruction[0].as_str() {
+        "ps" => {
+            messages.extend(scheduler.ps().lines().map(String::from));
+            Some(scheduler.running_pid)
+        }
+        // `dry <command>` Prints What The Command Would Do Without Running It
+        "dry" if instruction.len() > 1 => {
+            let dry_run = scheduler.dry_run(&parse_command(scheduler, &instruction[1..])?);
+            messages.push(match dry_run.refusal {
+                Some(refusal) => format!(
+                    "{}: refused ({})",
+                    instruction[1..].join(" "),
+                    ErrorKind::from(refusal).as_str()
+                ),
+                None => format!(
+                    "{}: running {}, blocked {:?}, woken {:?}, destroyed {:?}",
+                    instruction[1..].join(" "),
+                    dry_run
+                        .running
+                        .map_or_else(|| String::from("-1"), |pid| pid.to_string()),
+                    dry_run.blocked,
+                    dry_run.woken,
+                    dry_run.destroyed
+                ),
+            });
+            Some(scheduler.running_pid)
+        }
+        "recover" => {
+            let Some(strategy) = instruction.get(1).and_then(|x| RecoveryStrategy::parse(x)) else {
+                eprintln!("SHELL: Unknown Recovery Strategy");
+                return Err(ErrorKind::InvalidArgument);
+            };
+
+            // The Actions Are Reported From The Recovery Log
+            scheduler.recover(strategy);
+            Some(scheduler.running_pid)
+        }
+        _ => scheduler.apply(&parse_command(scheduler, instruction)?),
+    };
+
+    // Only `apply` Returns `None`, And It Records Why
+    result.ok_or_else(|| {
+        scheduler
+            .last_refusal()
+            .map_or(ErrorKind::Rejected, ErrorKind::from)
+    })
+}
+
+/// State saved by `begin`, restored by `rollback` and dropped by `commit`
+struct Transaction {
+    scheduler: Scheduler,
+    variables: HashMap<String, usize>,
+}
+
+fn control_transaction(
+    scheduler: &mut Scheduler,
+    keyword: &str,
+    transaction: &mut Option<Transaction>,
+    variables: &mut HashMap<String, usize>,
+) -> Result<usize, ErrorKind> {
+    match (keyword, transaction.take()) {
+        ("begin", None) => {
+            *transaction = Some(Transaction {
+                scheduler: scheduler.clone(),
+                variables: variables.clone(),
+            });
+        }
+        ("begin", Some(open)) => {
+            eprintln!("SHELL: Transaction Already In Progress");
+            *transaction = Some(open);
+            return Err(ErrorKind::InvalidState);
+        }
+        ("commit", Some(_)) => {}
+        ("rollback", Some(saved)) => {
+            *scheduler = saved.scheduler;
+            *variables = saved.variables;
+        }
+        _ => {
+            eprintln!("SHELL: No Transaction In Progress");
+            return Err(ErrorKind::InvalidState);
+        }
     }
 
-    Ok(instruction_vectors)
+    Ok(scheduler.running_pid)
 }
 
 fn handle_instruction_vector(
     scheduler: &mut Scheduler,
-    instruction_vector: &Vec<Vec<String>>,
-) -> Vec<Option<usize>> {
+    instruction_vector: &[Instruction],
+    format: OutputFormat,
+    failures: &mut usize,
+) -> Vec<Record> {
     let mut output = Vec::new();
+    let mut variables = HashMap::new();
+    let mut transaction = None;
 
     // Reset Scheduler
     scheduler.init();
 
-    for instruction in instruction_vector {
-        let result = match instruction[0].as_str() {
-            "in" => scheduler.init(),
-            "cr" => scheduler.create(instruction[1].parse().expect("Invalid Argument")),
-            "de" => scheduler.destroy(instruction[1].parse().expect("Invalid Argument")),
-            "rq" => scheduler.request(
-                instruction[1].parse().expect("Invalid Argument"),
-                instruction[2].parse().expect("Invalid Argument"),
-            ),
-            "rl" => scheduler.release(
-                instruction[1].parse().expect("Invalid Argument"),
-                instruction[2].parse().expect("Invalid Argument"),
-            ),
-            "to" => scheduler.timeout(),
-            _ => None,
+    for Instruction { line, words } in instruction_vector {
+        let before = (format == OutputFormat::Verbose).then(|| Snapshot::new(scheduler));
+
+        // `name = cr ...` Stores The PID Of The Created Process
+        let (variable, instruction) = match words.as_slice() {
+            [name, equals, command @ ..] if equals == "=" => (Some(name), command),
+            _ => (None, words.as_slice()),
+        };
+
+        let substituted = substitute(instruction, &variables);
+        let instruction = substituted.as_deref().unwrap_or(instruction);
+
+        let created_pid = scheduler.next_pid();
+        let logged = scheduler.recovery_log().len();
+        let mut messages = Vec::new();
+
+        let result = if variable.is_some() && instruction.first().map(String::as_str) != Some("cr")
+        {
+            eprintln!("SHELL: Only cr Can Be Assigned To A Variable");
+            Err(ErrorKind::InvalidAssignment)
+        } else if substituted.is_none() {
+            eprintln!("SHELL: Undefined Variable");
+            Err(ErrorKind::UndefinedVariable)
+        } else if matches!(instruction[0].as_str(), "begin" | "commit" | "rollback") {
+            control_transaction(scheduler, &instruction[0], &mut transaction, &mut variables)
+        } else if instruction[0] == "expect" {
+            check_expectation(scheduler, &instruction[1..])
+                .map(|()| scheduler.running_pid)
+                .map_err(|message| {
+                    eprintln!("EXPECT: Line {line}: {message}");
+                    *failures += 1;
+                    ErrorKind::FailedExpectation
+                })
+        } else {
+            execute(scheduler, instruction, &mut messages)
         };
 
-        output.push(result);
+        // Recovery Triggered By The Command, Like A Request Closing A Cycle, Is Reported Too
+        messages.extend(
+            scheduler
+                .recovery_log()
+                .get(logged..)
+                .unwrap_or_default()
+                .iter()
+                .map(ToString::to_string),
+        );
+
+        if let (Some(name), Ok(_), Some(pid)) = (variable, result, created_pid) {
+            variables.insert(name.clone(), pid);
+        }
+
+        output.push(Record {
+            line: *line,
+            opcode: instruction.first().cloned().unwrap_or_default(),
+            arguments: instruction.get(1..).unwrap_or_default().to_vec(),
+            error: result.err(),
+            // Report The Running PID Of Every Core
+            running: (0..scheduler.cores())
+                .filter_map(|core| scheduler.running(core))
+                .collect(),
+            names: (0..scheduler.cores())
+                .filter_map(|core| scheduler.running(core))
+                .map(|pid| scheduler.process(pid).and_then(|pcb| pcb.name.clone()))
+                .collect(),
+            messages,
+            changes: before
+                .map_or_else(Vec::new, |before| before.changes(&Snapshot::new(scheduler))),
+        });
     }
 
     output
 }
 
-fn write_output(filename: &str, output: &[Vec<Option<usize>>]) -> io::Result<()> {
-    let path = Path::new(filename);
-    let file = File::create(path)?;
-    let mut writer = BufWriter::new(file);
-    let last_index = output.len() - 1;
+#[derive(Debug, Clone)]
+pub struct ShellOptions {
+    pub format: OutputFormat,
+    /// Worker threads running batches in parallel, 1 runs them in order on the calling thread
+    pub jobs: usize,
+    /// Every batch starts from a scheduler with this configuration
+    pub config: Config,
+}
+
+impl Default for ShellOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            jobs: 1,
+            config: Config::default(),
+        }
+    }
+}
+
+/// Batches read ahead for each worker thread
+const BATCHES_PER_JOB: usize = 64;
 
-    for (i, batch) in output.iter().enumerate() {
-        let batch_str = batch
+/// Runs the batches on up to `jobs` threads, each with its own scheduler, keeping their order
+fn run_batches(
+    instruction_vectors: &[Vec<Instruction>],
+    options: &ShellOptions,
+) -> (Vec<Vec<Record>>, usize) {
+    let ShellOptions {
+        format,
+        jobs,
+        ref config,
+    } = *options;
+    let run = |instruction_vectors: &[Vec<Instruction>]| {
+        let mut scheduler = Scheduler::with_config(config.clone());
+        let mut failures = 0;
+        let output = instruction_vectors
             .iter()
-            .map(|i| {
-                i.map(|i| i.to_string())
-                    .unwrap_or_else(|| String::from("-1"))
+            .map(|instruction_vector| {
+                handle_instruction_vector(&mut scheduler, instruction_vector, format, &mut failures)
             })
-            .collect::<Vec<_>>()
-            .join(" ");
+            .collect::<Vec<_>>();
 
-        if i == last_index {
-            write!(writer, "{batch_str} ")?;
-        } else {
-            write!(writer, "{batch_str}\r\n")?;
-        }
+        (output, failures)
+    };
+
+    if jobs <= 1 {
+        return run(instruction_vectors);
     }
 
-    Ok(())
+    // Every Batch Starts With A Reset, So Contiguous Slices Can Run Independently
+    let slice_size = instruction_vectors.len().div_ceil(jobs).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = instruction_vectors
+            .chunks(slice_size)
+            .map(|slice| scope.spawn(move || run(slice)))
+            .collect();
+
+        let mut output = Vec::new();
+        let mut failures = 0;
+        for handle in handles {
+            let (slice_output, slice_failures) = handle
+                .join()
+                .unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+            output.extend(slice_output);
+            failures += slice_failures;
+        }
+
+        (output, failures)
+    })
 }
 
+/// # Errors
+///
+/// Will return `Err` if the input file cannot be read, the output file cannot be written or an
+/// `expect` assertion fails
 pub fn interactive_shell(input_filename: &str, output_filename: &str) -> Result<(), &'static str> {
-    let mut scheduler = Scheduler::new();
+    interactive_shell_with(input_filename, output_filename, &ShellOptions::default())
+}
 
-    let instruction_vectors = match read_file(input_filename) {
-        Ok(instruction_vectors) => instruction_vectors,
-        Err(error) => match error.kind() {
-            io::ErrorKind::NotFound => {
-                return Err("Input file (input.txt) not found in project root")
-            }
-            _ => {
-                eprintln!("{}", error);
-                return Err("Error reading input file");
-            }
-        },
+/// # Errors
+///
+/// Will return `Err` if the input file cannot be read, the output file cannot be written or an
+/// `expect` assertion fails
+pub fn interactive_shell_with(
+    input_filename: &str,
+    output_filename: &str,
+    options: &ShellOptions,
+) -> Result<(), &'static str> {
+    let input = match File::open(Path::new(input_filename)) {
+        Ok(input) => input,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Err("Input file (input.txt) not found in project root")
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            return Err("Error reading input file");
+        }
     };
 
-    let output: Vec<Vec<Option<usize>>> = instruction_vectors
-        .iter()
-        .map(|instruction_vector| handle_instruction_vector(&mut scheduler, instruction_vector))
-        .collect();
+    let writing_error = |error: io::Error| {
+        eprintln!("{error}");
+        "Error writing to output file"
+    };
 
-    match write_output(output_filename, &output) {
-        Ok(_) => (),
-        Err(error) => {
-            eprintln!("{}", error);
-            return Err("Error writing to output file");
+    let output = File::create(Path::new(output_filename)).map_err(writing_error)?;
+    let mut writer = RecordWriter::new(BufWriter::new(output), options.format);
+
+    // Each Chunk Of Batches Is Written And Flushed Before The Next One Is Read
+    let chunk_size = if options.jobs <= 1 {
+        1
+    } else {
+        options.jobs * BATCHES_PER_JOB
+    };
+    let mut batches = read_batches(BufReader::new(input));
+    let mut failures = 0;
+    loop {
+        let chunk: Vec<_> = batches.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+
+        let mut instruction_vectors = Vec::new();
+        let mut reading_error = None;
+        for instruction_vector in chunk {
+            match instruction_vector {
+                Ok(instruction_vector) => instruction_vectors.push(instruction_vector),
+                Err(error) => {
+                    reading_error = Some(error);
+                    break;
+                }
+            }
+        }
+
+        let (output, chunk_failures) = match panic::catch_unwind(AssertUnwindSafe(|| {
+            run_batches(&instruction_vectors, options)
+        })) {
+            Ok(result) => result,
+            Err(payload) => {
+                // Close The Batches Written So Far Before Passing The Panic On
+                writer.finish().map_err(writing_error)?;
+                panic::resume_unwind(payload);
+            }
+        };
+        failures += chunk_failures;
+        for records in &output {
+            writer.write_batch(records).map_err(writing_error)?;
+        }
+
+        if let Some(error) = reading_error {
+            eprintln!("{error}");
+            // Keep The Batches Written So Far
+            writer.finish().map_err(writing_error)?;
+            return Err("Error reading input file");
         }
     }
 
+    writer.finish().map_err(writing_error)?;
+
+    if failures > 0 {
+        return Err("One or more expect assertions failed");
+    }
+
     Ok(())
 }
@@ -0,0 +1,72 @@
+use crate::scheduler::config::SchedulingPolicy;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Burst {
+    /// Predicted length of the next CPU burst, `Config::initial_burst` when `None`
+    pub estimate: Option<usize>,
+    /// Ticks run so far in the current burst
+    pub elapsed: usize,
+}
+
+impl Scheduler {
+    pub(super) const fn tracks_bursts(&self) -> bool {
+        matches!(
+            self.config.policy,
+            SchedulingPolicy::ShortestJobFirst | SchedulingPolicy::ShortestRemainingTimeFirst
+        )
+    }
+
+    pub(super) fn burst_estimate(&self, pid: usize) -> usize {
+        self.pcb_list[pid]
+            .as_ref()
+            .and_then(|pcb| pcb.burst.estimate)
+            .unwrap_or(self.config.initial_burst)
+    }
+
+    pub(super) fn charge_burst(&mut self, pid: usize) {
+        if !self.tracks_bursts() || pid == 0 {
+            return;
+        }
+
+        if let Some(pcb) = self.pcb_list[pid].as_mut() {
+            pcb.burst.elapsed += 1;
+        }
+    }
+
+    /// Exponential averaging, `next = a * actual + (1 - a) * estimate` with `a` in percent
+    pub(super) fn end_burst(&mut self, pid: usize) {
+        if !self.tracks_bursts() {
+            return;
+        }
+
+        let estimate = self.burst_estimate(pid);
+        let weight = self.config.burst_weight.min(100);
+        let burst = &mut self.pcb_list[pid]
+            .as_mut()
+            .expect("BURST: PCB should exist.")
+            .burst;
+
+        burst.estimate = Some((weight * burst.elapsed + (100 - weight) * estimate) / 100);
+        burst.elapsed = 0;
+    }
+
+    /// Shortest predicted burst first, the running processes keep their cores unless preemptive
+    pub(super) fn sort_by_burst(&self, candidates: &mut [usize]) {
+        let running = |pid: usize| self.cpu_list.iter().any(|cpu| cpu.running_pid == pid);
+        let remaining = |pid: usize| {
+            let elapsed = self.pcb_list[pid]
+                .as_ref()
+                .map_or(0, |pcb| pcb.burst.elapsed);
+
+            self.burst_estimate(pid).saturating_sub(elapsed)
+        };
+
+        // Process 0 Only Runs When Nothing Else Is Ready
+        if self.config.policy == SchedulingPolicy::ShortestJobFirst {
+            candidates.sort_by_key(|&pid| (pid == 0, !running(pid), self.burst_estimate(pid)));
+        } else {
+            candidates.sort_by_key(|&pid| (pid == 0, remaining(pid), !running(pid)));
+        }
+    }
+}
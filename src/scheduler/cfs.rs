@@ -0,0 +1,49 @@
+use crate::scheduler::config::SchedulingPolicy;
+use crate::scheduler::Scheduler;
+
+/// Load weight of each `ready_list` priority level, heavier processes accumulate vruntime slower
+pub const WEIGHTS: [usize; 3] = [1, 2, 4];
+/// Virtual runtime a weight 1 process accumulates per tick
+pub const TICK_VRUNTIME: usize = 4;
+/// How far behind `min_vruntime` a woken process may be placed
+pub const SLEEPER_CREDIT: usize = TICK_VRUNTIME / 2;
+
+impl Scheduler {
+    /// Charges a process for one tick on a core, weighted by its priority
+    pub(super) fn charge(&mut self, pid: usize) {
+        if self.config.policy != SchedulingPolicy::CompletelyFair || pid == 0 {
+            return;
+        }
+
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("CHARGE: PCB should exist.");
+
+        let queued = self.cfs_queue.remove(&(pcb.vruntime, pid));
+        pcb.vruntime += TICK_VRUNTIME / WEIGHTS[pcb.priority];
+
+        if queued {
+            self.cfs_queue.insert((pcb.vruntime, pid));
+        }
+
+        // Only Ever Moves Forward
+        if let Some(&(vruntime, _)) = self.cfs_queue.first() {
+            self.min_vruntime = self.min_vruntime.max(vruntime);
+        }
+    }
+
+    /// Stops a long sleeper from monopolizing the cores with a stale, small vruntime
+    pub(super) fn place_woken(&mut self, pid: usize) {
+        if self.config.policy != SchedulingPolicy::CompletelyFair {
+            return;
+        }
+
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("PLACE: PCB should exist.");
+
+        pcb.vruntime = pcb
+            .vruntime
+            .max(self.min_vruntime.saturating_sub(SLEEPER_CREDIT));
+    }
+}
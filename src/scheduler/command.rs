@@ -0,0 +1,192 @@
+use crate::scheduler::pcb::{CreateOptions, PCBState};
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+/// A scheduler operation as a value, so it can be previewed before it is applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Init,
+    Create {
+        priority: i32,
+        options: CreateOptions,
+    },
+    Destroy(i32),
+    /// Blocks for at most `timeout` clock ticks, a timeout of 0 fails instead of blocking
+    Request {
+        rid: i32,
+        units: i32,
+        timeout: Option<i32>,
+    },
+    Release {
+        rid: i32,
+        units: i32,
+    },
+    /// Times out the current core
+    Timeout,
+    TimeoutOn(i32),
+    CreatePeriodic {
+        period: i32,
+        execution: i32,
+        deadline: Option<i32>,
+    },
+    SwitchCpu(i32),
+    Suspend(i32),
+    Resume(i32),
+    Exit(i32),
+    Wait(i32),
+    Lock(i32),
+    Unlock(i32),
+    SetSemaphore {
+        sid: i32,
+        value: i32,
+    },
+    SemaphoreWait(i32),
+    SemaphoreSignal(i32),
+    Send {
+        pid: i32,
+        value: i32,
+    },
+    Receive,
+    Io {
+        dev: i32,
+        duration: i32,
+    },
+    MakeResource {
+        inventory: i32,
+        name: Option<String>,
+    },
+    AddUnits {
+        rid: i32,
+        units: i32,
+    },
+    /// Held units are only taken back with `preempt`
+    RemoveUnits {
+        rid: i32,
+        units: i32,
+        preempt: bool,
+    },
+    SetGroup {
+        pid: i32,
+        group: i32,
+    },
+    DestroyGroup(i32),
+    SuspendGroup(i32),
+    ResumeGroup(i32),
+    SetGroupPriority {
+        group: i32,
+        priority: i32,
+    },
+}
+
+/// What a command would do, see `Scheduler::dry_run`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryRun {
+    /// Running PID afterwards, `None` if the command would be rejected
+    pub running: Option<usize>,
+    /// Processes that would become blocked
+    pub blocked: Vec<usize>,
+    /// Blocked processes that would become ready
+    pub woken: Vec<usize>,
+    /// Processes that would be removed from the process table
+    pub destroyed: Vec<usize>,
+    /// Why the command would be refused, if the scheduler gave a reason
+    pub refusal: Option<Refusal>,
+}
+
+const fn is_blocked(state: PCBState) -> bool {
+    matches!(state, PCBState::BLOCKED | PCBState::SUSPENDED_BLOCKED)
+}
+
+impl Scheduler {
+    /// Runs the command, returning the running PID like the method it stands for.
+    /// `last_refusal` tells why it returned `None`.
+    pub fn apply(&mut self, command: &Command) -> Option<usize> {
+        self.refusal = None;
+
+        match *command {
+            Command::Init => self.init(),
+            Command::Create {
+                priority,
+                ref options,
+            } => self.create_with(priority, options),
+            Command::Destroy(pid) => self.destroy(pid),
+            Command::Request {
+                rid,
+                units,
+                timeout,
+            } => self.request_within(rid, units, timeout),
+            Command::Release { rid, units } => self.release(rid, units),
+            Command::Timeout => self.timeout(),
+            Command::TimeoutOn(core) => self.timeout_on(core),
+            Command::CreatePeriodic {
+                period,
+                execution,
+                deadline,
+            } => self.create_periodic(period, execution, deadline),
+            Command::SwitchCpu(core) => self.switch_cpu(core),
+            Command::Suspend(pid) => self.suspend(pid),
+            Command::Resume(pid) => self.resume(pid),
+            Command::Exit(code) => self.exit(code),
+            Command::Wait(pid) => self.wait(pid),
+            Command::Lock(mid) => self.lock(mid),
+            Command::Unlock(mid) => self.unlock(mid),
+            Command::SetSemaphore { sid, value } => self.set_semaphore(sid, value),
+            Command::SemaphoreWait(sid) => self.semaphore_wait(sid),
+            Command::SemaphoreSignal(sid) => self.semaphore_signal(sid),
+            Command::Send { pid, value } => self.send(pid, value),
+            Command::Receive => self.receive(),
+            Command::Io { dev, duration } => self.io(dev, duration),
+            Command::MakeResource {
+                inventory,
+                ref name,
+            } => self.make_resource(inventory, name.as_deref()),
+            Command::AddUnits { rid, units } => self.add_units(rid, units),
+            Command::RemoveUnits {
+                rid,
+                units,
+                preempt,
+            } => self.remove_units(rid, units, preempt),
+            Command::SetGroup { pid, group } => self.set_group(pid, group),
+            Command::DestroyGroup(group) => self.destroy_group(group),
+            Command::SuspendGroup(group) => self.suspend_group(group),
+            Command::ResumeGroup(group) => self.resume_group(group),
+            Command::SetGroupPriority { group, priority } => {
+                self.set_group_priority(group, priority)
+            }
+        }
+    }
+
+    /// Applies the command to a copy of the scheduler and reports the difference,
+    /// leaving this scheduler untouched
+    #[must_use]
+    pub fn dry_run(&self, command: &Command) -> DryRun {
+        let mut after = self.clone();
+        let Some(running) = after.apply(command) else {
+            return DryRun {
+                refusal: after.last_refusal(),
+                ..DryRun::default()
+            };
+        };
+
+        let mut dry_run = DryRun {
+            running: Some(running),
+            ..DryRun::default()
+        };
+
+        for (pid, (before, after)) in self.pcb_list.iter().zip(&after.pcb_list).enumerate() {
+            match (before, after) {
+                (Some(_), None) => dry_run.destroyed.push(pid),
+                (Some(before), Some(after)) => {
+                    if !is_blocked(before.state) && is_blocked(after.state) {
+                        dry_run.blocked.push(pid);
+                    } else if is_blocked(before.state) && !is_blocked(after.state) {
+                        dry_run.woken.push(pid);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        dry_run
+    }
+}
@@ -0,0 +1,250 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExitPolicy {
+    /// Destroy the exiting process's whole subtree, like `destroy`
+    #[default]
+    Cascade,
+    /// Hand the children over to process 0
+    ReparentToInit,
+    /// Hand the children over to the exiting process's parent
+    ReparentToGrandparent,
+}
+
+impl ExitPolicy {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "cascade" => Some(Self::Cascade),
+            "reparent-to-init" => Some(Self::ReparentToInit),
+            "reparent-to-grandparent" => Some(Self::ReparentToGrandparent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueMode {
+    /// Every core picks from the one `ready_list`
+    #[default]
+    Shared,
+    /// Each process is queued on a single core and only runs there
+    PerCore,
+}
+
+impl QueueMode {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "shared" => Some(Self::Shared),
+            "per-core" => Some(Self::PerCore),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BalanceStrategy {
+    /// Processes stay on the core they were placed on
+    #[default]
+    Disabled,
+    /// Every `period` clock ticks, move queued processes from the busiest core to the idlest
+    PushMigration { period: usize },
+    /// A core with nothing to run takes a queued process from another core
+    WorkStealing,
+}
+
+impl BalanceStrategy {
+    /// Parses `disabled`, `stealing` or `push`, optionally followed by a period such as
+    /// `push:4`
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.split_once(':') {
+            Some(("push", period)) => Some(Self::PushMigration {
+                period: period.parse().ok()?,
+            }),
+            Some(_) => None,
+            None => match name {
+                "disabled" => Some(Self::Disabled),
+                "push" => Some(Self::PushMigration { period: 1 }),
+                "stealing" => Some(Self::WorkStealing),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchedulingPolicy {
+    /// Fixed priority levels of the `ready_list`, round robin within a level
+    #[default]
+    Priority,
+    /// Periodic tasks by earliest absolute deadline, ahead of other processes
+    EarliestDeadlineFirst,
+    /// Periodic tasks by shortest period, ahead of other processes
+    RateMonotonic,
+    /// Smallest priority-weighted virtual runtime first
+    CompletelyFair,
+    /// Shortest predicted CPU burst first, never preempts the running process
+    ShortestJobFirst,
+    /// Shortest predicted remaining CPU burst first, preempting the running process
+    ShortestRemainingTimeFirst,
+}
+
+impl SchedulingPolicy {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "priority" => Some(Self::Priority),
+            "edf" => Some(Self::EarliestDeadlineFirst),
+            "rate-monotonic" => Some(Self::RateMonotonic),
+            "cfs" => Some(Self::CompletelyFair),
+            "sjf" => Some(Self::ShortestJobFirst),
+            "srtf" => Some(Self::ShortestRemainingTimeFirst),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VictimPolicy {
+    #[default]
+    LowestPriority,
+    /// Fewest resource units held in total
+    FewestUnits,
+    /// Highest PID
+    Youngest,
+}
+
+impl VictimPolicy {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "lowest-priority" => Some(Self::LowestPriority),
+            "fewest-units" => Some(Self::FewestUnits),
+            "youngest" => Some(Self::Youngest),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryStrategy {
+    /// Take back the victim's resources and queue its pending request again
+    Rollback(VictimPolicy),
+    /// Destroy the victim and its descendants
+    Terminate(VictimPolicy),
+}
+
+impl RecoveryStrategy {
+    /// Parses `rollback` or `terminate`, optionally followed by a victim policy such as
+    /// `terminate:fewest-units`
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let (strategy, policy) = name.split_once(':').unwrap_or((name, "lowest-priority"));
+        let policy = VictimPolicy::parse(policy)?;
+
+        match strategy {
+            "rollback" => Some(Self::Rollback(policy)),
+            "terminate" => Some(Self::Terminate(policy)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceConfig {
+    /// Lets commands refer to the resource by name instead of its RID
+    pub name: Option<String>,
+    pub inventory: usize,
+}
+
+impl ResourceConfig {
+    #[must_use]
+    pub const fn new(inventory: usize) -> Self {
+        Self {
+            name: None,
+            inventory,
+        }
+    }
+
+    /// Parses a comma-separated list such as `disk=2, printer=1`, where an entry without
+    /// a name is just its inventory
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an entry has no valid inventory, a name is a number or a name is
+    /// used twice
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        let mut resources: Vec<Self> = Vec::new();
+
+        for entry in list.split(',').map(str::trim) {
+            let (name, inventory) = match entry.split_once('=') {
+                Some((name, inventory)) => (Some(name.trim()), inventory.trim()),
+                None => (None, entry),
+            };
+
+            let inventory = inventory
+                .parse()
+                .map_err(|_| format!("Invalid inventory in resource {entry}"))?;
+
+            if let Some(name) = name {
+                if name.is_empty() || name.parse::<i64>().is_ok() {
+                    return Err(format!("Invalid resource name in {entry}"));
+                }
+
+                if resources
+                    .iter()
+                    .any(|resource| resource.name.as_deref() == Some(name))
+                {
+                    return Err(format!("Duplicate resource name {name}"));
+                }
+            }
+
+            resources.push(Self {
+                name: name.map(String::from),
+                inventory,
+            });
+        }
+
+        Ok(resources)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub exit_policy: ExitPolicy,
+    /// Maximum messages queued per mailbox, unbounded when `None`
+    pub mailbox_capacity: Option<usize>,
+    pub cores: usize,
+    pub queue_mode: QueueMode,
+    /// Only used with per-core queues
+    pub balancing: BalanceStrategy,
+    pub policy: SchedulingPolicy,
+    /// Burst estimate of a process created without one
+    pub initial_burst: usize,
+    /// Weight of the last actual burst in the exponential average, in percent
+    pub burst_weight: usize,
+    /// Size of the process table, including process 0
+    pub processes: usize,
+    /// One RCB per entry, in RID order
+    pub resources: Vec<ResourceConfig>,
+    /// Breaks deadlocks as soon as a blocking request causes one
+    pub deadlock_recovery: Option<RecoveryStrategy>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            exit_policy: ExitPolicy::default(),
+            mailbox_capacity: None,
+            cores: 1,
+            queue_mode: QueueMode::default(),
+            balancing: BalanceStrategy::default(),
+            policy: SchedulingPolicy::default(),
+            initial_burst: 5,
+            burst_weight: 50,
+            processes: 16,
+            resources: [1, 1, 2, 3].map(ResourceConfig::new).to_vec(),
+            deadlock_recovery: None,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::scheduler::config::{BalanceStrategy, QueueMode, SchedulingPolicy};
+use crate::scheduler::pcb::PCB;
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cpu {
+    /// Process 0 doubles as the idle process, so an idle core runs it too
+    pub running_pid: usize,
+    pub busy_ticks: usize,
+    pub idle_ticks: usize,
+    /// Processes moved onto this core by load balancing
+    pub migrations: usize,
+}
+
+impl Cpu {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            running_pid: 0,
+            busy_ticks: 0,
+            idle_ticks: 0,
+            migrations: 0,
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn switch_cpu(&mut self, core: i32) -> Option<usize> {
+        let core = usize::try_from(core).ok()?;
+
+        // Bounds Check
+        if core >= self.cpu_list.len() {
+            self.refuse(Refusal::UnknownId, "CPU: Core Out Of Bounds");
+            return None;
+        }
+
+        self.current_core = core;
+        self.running_pid = self.cpu_list[core].running_pid;
+
+        Some(self.running_pid)
+    }
+
+    fn can_run_on(&self, pcb: &PCB, core: usize) -> bool {
+        match self.config.queue_mode {
+            QueueMode::Shared => pcb.affinity.is_none_or(|affinity| affinity == core),
+            // Process 0 Isn't Queued On A Core, So It May Take Its Turn On Any
+            QueueMode::PerCore => pcb.core.is_none_or(|x| x == core),
+        }
+    }
+
+    /// Picks the core a new process is queued on in per-core mode
+    pub(super) fn place(&self, affinity: Option<usize>) -> Option<usize> {
+        if self.config.queue_mode == QueueMode::Shared {
+            return None;
+        }
+
+        affinity.or_else(|| {
+            (0..self.cpu_list.len()).min_by_key(|&core| {
+                self.ready_list
+                    .iter()
+                    .flatten()
+                    .filter(|&pid| {
+                        self.pcb_list[pid]
+                            .as_ref()
+                            .is_some_and(|pcb| pcb.core == Some(core))
+                    })
+                    .count()
+            })
+        })
+    }
+
+    pub(super) fn account_cpu_time(&mut self) {
+        for cpu in &mut self.cpu_list {
+            if cpu.running_pid == 0 {
+                cpu.idle_ticks += 1;
+            } else {
+                cpu.busy_ticks += 1;
+            }
+        }
+    }
+
+    fn queued_on(&self, core: usize) -> impl Iterator<Item = usize> + '_ {
+        self.ready_list.iter().rev().flatten().filter(move |&pid| {
+            pid != 0
+                && self.pcb_list[pid]
+                    .as_ref()
+                    .is_some_and(|pcb| pcb.core == Some(core))
+        })
+    }
+
+    fn is_migratable(&self, pid: usize) -> bool {
+        self.pcb_list[pid]
+            .as_ref()
+            .is_some_and(|pcb| pcb.affinity.is_none())
+            && self.cpu_list.iter().all(|cpu| cpu.running_pid != pid)
+    }
+
+    fn migrate(&mut self, pid: usize, core: usize) {
+        self.pcb_list[pid]
+            .as_mut()
+            .expect("MIGRATE: PCB should exist.")
+            .core = Some(core);
+        self.cpu_list[core].migrations += 1;
+    }
+
+    /// Moves queued processes from the busiest core to the idlest until their loads differ by at most one
+    pub(super) fn push_migrate(&mut self) {
+        loop {
+            let loads: Vec<usize> = (0..self.cpu_list.len())
+                .map(|core| self.queued_on(core).count())
+                .collect();
+            let (Some(busiest), Some(idlest)) = (
+                (0..loads.len()).max_by_key(|&core| loads[core]),
+                (0..loads.len()).min_by_key(|&core| loads[core]),
+            ) else {
+                return;
+            };
+
+            if loads[busiest] <= loads[idlest] + 1 {
+                return;
+            }
+
+            // Move The Least Important Process That Isn't Running
+            let Some(pid) = self
+                .queued_on(busiest)
+                .filter(|&pid| self.is_migratable(pid))
+                .last()
+            else {
+                return;
+            };
+
+            self.migrate(pid, idlest);
+        }
+    }
+
+    /// Ready processes, most important first
+    fn candidates(&self) -> Vec<usize> {
+        let single_core = self.cpu_list.len() == 1;
+
+        if self.config.policy == SchedulingPolicy::CompletelyFair {
+            // One Core Only Needs The Smallest Virtual Runtime
+            if single_core {
+                return self
+                    .cfs_queue
+                    .first()
+                    .map(|&(_, pid)| pid)
+                    .into_iter()
+                    .collect();
+            }
+
+            return self.cfs_queue.iter().map(|&(_, pid)| pid).collect();
+        }
+
+        // One Core Under Fixed Priorities Only Needs The Front Of The Highest Level
+        if single_core && self.config.policy == SchedulingPolicy::Priority {
+            return self
+                .ready_list
+                .highest()
+                .and_then(|level| self.ready_list.front(level))
+                .into_iter()
+                .collect();
+        }
+
+        let mut candidates: Vec<usize> = self.ready_list.iter().rev().flatten().collect();
+
+        // The Sorts Are Stable So Ties Keep Ready List Order
+        match self.config.policy {
+            SchedulingPolicy::Priority | SchedulingPolicy::CompletelyFair => {}
+            SchedulingPolicy::EarliestDeadlineFirst | SchedulingPolicy::RateMonotonic => {
+                // Periodic Tasks Come First
+                candidates.sort_by_key(|&pid| {
+                    self.deadline_order(pid).map_or((1, 0), |order| (0, order))
+                });
+            }
+            SchedulingPolicy::ShortestJobFirst | SchedulingPolicy::ShortestRemainingTimeFirst => {
+                self.sort_by_burst(&mut candidates);
+            }
+        }
+
+        candidates
+    }
+
+    /// Assigns the highest priority ready processes to the cores they may run on.
+    /// A process keeps its core while it stays selected, otherwise the core running
+    /// the least important process is preempted first.
+    pub(super) fn dispatch(&mut self) {
+        let candidates = self.candidates();
+        // An Idle Core Is Taken Before Any Busy One
+        let rank = |pid: usize| {
+            candidates
+                .iter()
+                .position(|&x| x == pid)
+                .filter(|_| pid != 0)
+                .unwrap_or(usize::MAX)
+        };
+        let mut claimed: Vec<Option<usize>> = vec![None; self.cpu_list.len()];
+
+        for &pid in &candidates {
+            let pcb = self.pcb_list[pid]
+                .as_ref()
+                .expect("SCHEDULER: Ready PCB should exist.");
+
+            let core = (0..self.cpu_list.len())
+                .filter(|&core| claimed[core].is_none() && self.can_run_on(pcb, core))
+                .min_by_key(|&core| {
+                    let occupant = self.cpu_list[core].running_pid;
+                    (occupant != pid, std::cmp::Reverse(rank(occupant)))
+                });
+
+            if let Some(core) = core {
+                claimed[core] = Some(pid);
+            }
+        }
+
+        // Idle Cores Steal The Most Important Process Queued Elsewhere
+        if self.config.queue_mode == QueueMode::PerCore
+            && self.config.balancing == BalanceStrategy::WorkStealing
+        {
+            for core in 0..self.cpu_list.len() {
+                if claimed[core].is_some_and(|pid| pid != 0) {
+                    continue;
+                }
+
+                let stolen = candidates.iter().copied().find(|&pid| {
+                    pid != 0
+                        && !claimed.contains(&Some(pid))
+                        && self.pcb_list[pid]
+                            .as_ref()
+                            .is_some_and(|pcb| pcb.affinity.is_none() && pcb.core != Some(core))
+                });
+
+                if let Some(pid) = stolen {
+                    self.migrate(pid, core);
+                    claimed[core] = Some(pid);
+                }
+            }
+        }
+
+        for (cpu, pid) in self.cpu_list.iter_mut().zip(claimed) {
+            cpu.running_pid = pid.unwrap_or(0);
+        }
+    }
+}
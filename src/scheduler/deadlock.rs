@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::scheduler::config::{RecoveryStrategy, VictimPolicy};
+use crate::scheduler::pcb::PCBResource;
+use crate::scheduler::rcb::RCBResource;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// The victim gave up `released` and waits for its pending request again
+    RolledBack {
+        pid: usize,
+        released: Vec<PCBResource>,
+    },
+    /// The victim was destroyed along with its descendants
+    Terminated { pid: usize, destroyed: Vec<usize> },
+}
+
+impl fmt::Display for RecoveryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RolledBack { pid, released } => {
+                let released: Vec<String> = released
+                    .iter()
+                    .map(|resource| format!("{}:{}", resource.rid, resource.units))
+                    .collect();
+                write!(f, "rolled back {pid}, released [{}]", released.join(","))
+            }
+            Self::Terminated { pid, destroyed } => {
+                write!(f, "terminated {pid}, destroyed {destroyed:?}")
+            }
+        }
+    }
+}
+
+impl Scheduler {
+    /// Processes blocked on resources that can never be granted, even if every other
+    /// process finishes and releases what it holds
+    #[must_use]
+    pub fn deadlocked(&self) -> Vec<usize> {
+        let mut work: Vec<usize> = self
+            .rcb_list
+            .iter()
+            .map(|rcb| rcb.units_available)
+            .collect();
+
+        let mut waiting: Vec<Option<(usize, usize)>> = vec![None; self.pcb_list.len()];
+        for (rid, rcb) in self.rcb_list.iter().enumerate() {
+            for request in &rcb.waitlist {
+                waiting[request.pid] = Some((rid, request.units));
+            }
+        }
+
+        // Everything Not Waiting On A Resource Finishes Eventually
+        let release = |work: &mut Vec<usize>, pid: usize| {
+            for resource in self.pcb_list[pid].iter().flat_map(|pcb| &pcb.resources) {
+                work[resource.rid] += resource.units;
+            }
+        };
+        for (pid, request) in waiting.iter().enumerate() {
+            if request.is_none() {
+                release(&mut work, pid);
+            }
+        }
+
+        // Then Any Waiter Whose Request Fits, Until None Does
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for (pid, request) in waiting.iter_mut().enumerate() {
+                if let Some((rid, units)) = *request {
+                    if units <= work[rid] {
+                        *request = None;
+                        release(&mut work, pid);
+                        progress = true;
+                    }
+                }
+            }
+        }
+
+        (0..waiting.len())
+            .filter(|&pid| waiting[pid].is_some())
+            .collect()
+    }
+
+    fn choose_victim(&self, candidates: &[usize], policy: VictimPolicy) -> Option<usize> {
+        let held = |pid: usize| -> usize {
+            self.pcb_list[pid]
+                .iter()
+                .flat_map(|pcb| &pcb.resources)
+                .map(|resource| resource.units)
+                .sum()
+        };
+        let priority = |pid: usize| self.pcb_list[pid].as_ref().map_or(0, |pcb| pcb.priority);
+
+        // Ties Go To The Highest PID
+        candidates.iter().copied().min_by_key(|&pid| match policy {
+            VictimPolicy::LowestPriority => (priority(pid), std::cmp::Reverse(pid)),
+            VictimPolicy::FewestUnits => (held(pid), std::cmp::Reverse(pid)),
+            VictimPolicy::Youngest => (0, std::cmp::Reverse(pid)),
+        })
+    }
+
+    /// Takes back everything the victim holds and queues its pending request again,
+    /// granting it straight away if the released units are enough
+    fn roll_back(&mut self, pid: usize) -> Vec<PCBResource> {
+        let (rid, request) = self
+            .rcb_list
+            .iter_mut()
+            .enumerate()
+            .find_map(|(rid, rcb)| {
+                let position = rcb.waitlist.iter().position(|x| x.pid == pid)?;
+                rcb.waitlist.remove(position).map(|request| (rid, request))
+            })
+            .expect("DEADLOCK: Deadlocked process should be in a waitlist.");
+
+        let released = self.pcb_list[pid]
+            .as_ref()
+            .map(|pcb| pcb.resources.clone())
+            .unwrap_or_default();
+        for resource in &released {
+            self.release_units(pid, resource.rid, resource.units);
+        }
+
+        let rcb = &mut self.rcb_list[rid];
+        if rcb.units_available >= request.units {
+            rcb.units_available -= request.units;
+            if let Some(expires) = request.expires {
+                self.timers.remove(&(expires, pid));
+            }
+            self.pcb_list[pid]
+                .as_mut()
+                .expect("DEADLOCK: PCB should exist.")
+                .resources
+                .push(PCBResource {
+                    rid,
+                    units: request.units,
+                });
+            self.unblock(pid);
+        } else {
+            rcb.waitlist.push_back(RCBResource { pid, ..request });
+        }
+
+        released
+    }
+
+    /// Rolls back or terminates victims until no process is deadlocked. Every action is
+    /// appended to `recovery_log` and the ones taken by this call are returned.
+    pub fn recover(&mut self, strategy: RecoveryStrategy) -> Vec<RecoveryAction> {
+        let mut actions = Vec::new();
+        let mut rolled_back = Vec::new();
+
+        loop {
+            // A Victim Is Rolled Back At Most Once, So Recovery Always Ends
+            let candidates: Vec<usize> = self
+                .deadlocked()
+                .into_iter()
+                .filter(|pid| !rolled_back.contains(pid))
+                .collect();
+
+            let action = match strategy {
+                RecoveryStrategy::Rollback(policy) => {
+                    let Some(pid) = self.choose_victim(&candidates, policy) else {
+                        break;
+                    };
+                    rolled_back.push(pid);
+
+                    RecoveryAction::RolledBack {
+                        pid,
+                        released: self.roll_back(pid),
+                    }
+                }
+                RecoveryStrategy::Terminate(policy) => {
+                    let Some(pid) = self.choose_victim(&candidates, policy) else {
+                        break;
+                    };
+
+                    RecoveryAction::Terminated {
+                        pid,
+                        destroyed: self.tear_down(pid),
+                    }
+                }
+            };
+
+            actions.push(action);
+        }
+
+        if !actions.is_empty() {
+            self.scheduler();
+        }
+
+        self.recovery_log.extend(actions.iter().cloned());
+        actions
+    }
+}
@@ -1,8 +1,12 @@
+use crate::scheduler::config::ResourceConfig;
+use crate::scheduler::device::Device;
 use crate::scheduler::pcb::PCB;
+use crate::scheduler::ready::ReadyList;
+use crate::scheduler::sync::{Mutex, Semaphore};
 use crate::scheduler::RCB;
 
 #[must_use]
-pub fn pcb_list_default() -> [Option<PCB>; 16] {
+pub const fn pcb_list_default() -> [Option<PCB>; 16] {
     [
         Some(PCB::new(0, None)),
         None,
@@ -23,7 +27,56 @@ pub fn pcb_list_default() -> [Option<PCB>; 16] {
     ]
 }
 
+/// Process table of the given size holding only process 0
 #[must_use]
-pub fn rcb_list_default() -> [RCB; 4] {
+pub fn pcb_list_with(processes: usize) -> Vec<Option<PCB>> {
+    (0..processes)
+        .map(|pid| (pid == 0).then(|| PCB::new(0, None)))
+        .collect()
+}
+
+/// Three priority levels with process 0 queued in the lowest
+#[must_use]
+pub fn ready_list_with(processes: usize) -> ReadyList {
+    let mut ready_list = ReadyList::new(3, processes);
+    ready_list.push_back(0, 0);
+
+    ready_list
+}
+
+#[must_use]
+pub const fn rcb_list_default() -> [RCB; 4] {
     [RCB::new(1), RCB::new(1), RCB::new(2), RCB::new(3)]
 }
+
+/// One RCB per configured resource
+#[must_use]
+pub fn rcb_list_with(resources: &[ResourceConfig]) -> Vec<RCB> {
+    resources
+        .iter()
+        .map(|resource| RCB {
+            name: resource.name.clone(),
+            ..RCB::new(resource.inventory)
+        })
+        .collect()
+}
+
+#[must_use]
+pub const fn mutex_list_default() -> [Mutex; 4] {
+    [Mutex::new(), Mutex::new(), Mutex::new(), Mutex::new()]
+}
+
+#[must_use]
+pub const fn semaphore_list_default() -> [Semaphore; 4] {
+    [
+        Semaphore::new(0),
+        Semaphore::new(0),
+        Semaphore::new(0),
+        Semaphore::new(0),
+    ]
+}
+
+#[must_use]
+pub const fn device_list_default() -> [Device; 4] {
+    [Device::new(), Device::new(), Device::new(), Device::new()]
+}
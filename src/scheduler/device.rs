@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IORequest {
+    pub pid: usize,
+    pub remaining: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Device {
+    pub queue: VecDeque<IORequest>,
+}
+
+impl Device {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl Scheduler {
+    /// Blocks the running process on a device for `duration` clock ticks.
+    /// Requests are served one at a time in FIFO order and the clock advances on every `timeout`.
+    pub fn io(&mut self, dev: i32, duration: i32) -> Option<usize> {
+        let dev = usize::try_from(dev).ok()?;
+        let duration = usize::try_from(duration).ok()?;
+
+        // Bounds Check
+        if dev >= self.device_list.len() {
+            self.refuse(Refusal::UnknownId, "IO: Device Out Of Bounds");
+            return None;
+        }
+
+        if duration == 0 {
+            self.refuse(Refusal::InvalidArgument, "IO: Duration Cannot Be 0");
+            return None;
+        }
+
+        // Process 0 Can't Block
+        if self.running_pid == 0 {
+            self.refuse(
+                Refusal::NotPermitted,
+                "IO: Process 0 Can't Block On A Device",
+            );
+            return None;
+        }
+
+        self.device_list[dev].queue.push_back(IORequest {
+            pid: self.running_pid,
+            remaining: duration,
+        });
+        self.block_running();
+
+        Some(self.scheduler())
+    }
+
+    pub(super) fn complete_io(&mut self) {
+        for dev in 0..self.device_list.len() {
+            let Some(request) = self.device_list[dev].queue.front_mut() else {
+                continue;
+            };
+
+            request.remaining -= 1;
+
+            // Completion Interrupt
+            if request.remaining == 0 {
+                let pid = request.pid;
+                self.device_list[dev].queue.pop_front();
+                self.unblock(pid);
+            }
+        }
+    }
+}
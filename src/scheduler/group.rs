@@ -0,0 +1,169 @@
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+impl Scheduler {
+    /// Live processes in the group, by PID
+    pub fn group_members(&self, group: usize) -> impl Iterator<Item = usize> + '_ {
+        self.pcb_list
+            .iter()
+            .enumerate()
+            .filter(move |(_, pcb)| pcb.as_ref().is_some_and(|pcb| pcb.group == group))
+            .map(|(pid, _)| pid)
+    }
+
+    /// Moves a descendant of the current process into another group, which its future
+    /// children inherit
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the process has no PCB
+    pub fn set_group(&mut self, pid: i32, group: i32) -> Option<usize> {
+        let pid = usize::try_from(pid).ok()?;
+        let group = usize::try_from(group).ok()?;
+
+        // Bounds Check
+        if pid >= self.pcb_list.len() {
+            self.refuse(Refusal::UnknownId, "SETGROUP: PID Out Of Bounds");
+            return None;
+        }
+
+        if pid == 0 {
+            self.refuse(Refusal::NotPermitted, "SETGROUP: Cannot Move Process 0");
+            return None;
+        }
+
+        if self.pcb_list[pid].is_none() {
+            self.refuse(Refusal::UnknownId, "SETGROUP: PID Does Not Exist");
+            return None;
+        }
+
+        if !self.is_child_of_current_process(pid) {
+            self.refuse(
+                Refusal::NotPermitted,
+                "SETGROUP: PID Is Not A Child Of The Current Process",
+            );
+            return None;
+        }
+
+        self.pcb_list[pid]
+            .as_mut()
+            .expect("SETGROUP: PCB should exist.")
+            .group = group;
+
+        Some(self.running_pid)
+    }
+
+    /// Members of the group if the current process may act on all of them
+    fn permitted_members(&mut self, group: i32, command: &str) -> Option<Vec<usize>> {
+        let group = usize::try_from(group).ok()?;
+        let members: Vec<usize> = self.group_members(group).collect();
+
+        if members.is_empty() {
+            self.refuse(Refusal::InvalidState, &format!("{command}: Group Is Empty"));
+            return None;
+        }
+
+        // Process 0 Can't Be Acted On, Even In Its Own Group
+        if members.contains(&0) {
+            self.refuse(
+                Refusal::NotPermitted,
+                &format!("{command}: Group Contains Process 0"),
+            );
+            return None;
+        }
+
+        // Refuse The Whole Group Rather Than Acting On Part Of It
+        if let Some(pid) = members
+            .iter()
+            .find(|&&pid| !self.is_child_of_current_process(pid))
+        {
+            self.refuse(
+                Refusal::NotPermitted,
+                &format!("{command}: Process {pid} Is Not A Child Of The Current Process"),
+            );
+            return None;
+        }
+
+        Some(members)
+    }
+
+    /// Destroys every member and its descendants, rescheduling once at the end
+    pub fn destroy_group(&mut self, group: i32) -> Option<usize> {
+        let members = self.permitted_members(group, "DESTROYGROUP")?;
+
+        for pid in members {
+            // Already Gone With An Ancestor In The Group
+            if self.pcb_list[pid].is_some() {
+                self.tear_down(pid);
+            }
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// Suspends the members that aren't suspended or exited yet
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a member of the group has no PCB
+    pub fn suspend_group(&mut self, group: i32) -> Option<usize> {
+        let members = self.permitted_members(group, "SUSPENDGROUP")?;
+
+        for pid in members {
+            // Members Already Suspended Or Exited Are Left As They Are
+            let _ = self.suspend_one(pid);
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// Resumes the suspended members
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a member of the group has no PCB
+    pub fn resume_group(&mut self, group: i32) -> Option<usize> {
+        let members = self.permitted_members(group, "RESUMEGROUP")?;
+
+        for pid in members {
+            // Members That Aren't Suspended Are Left As They Are
+            let _ = self.resume_one(pid);
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// Moves every member to another priority level, ready members join the back of it
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a member of the group has no PCB
+    pub fn set_group_priority(&mut self, group: i32, priority: i32) -> Option<usize> {
+        let priority = usize::try_from(priority).ok()?;
+
+        // Bounds Check
+        if priority >= self.ready_list.len() {
+            self.refuse(
+                Refusal::InvalidArgument,
+                "GROUPPRIORITY: Priority Out Of Bounds",
+            );
+            return None;
+        }
+
+        let members = self.permitted_members(group, "GROUPPRIORITY")?;
+
+        for pid in members {
+            let queued = self.dequeue(pid);
+            self.pcb_list[pid]
+                .as_mut()
+                .expect("GROUPPRIORITY: Member PCB should exist.")
+                .priority = priority;
+
+            if queued {
+                self.enqueue(pid);
+            }
+        }
+
+        Some(self.scheduler())
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+
+use crate::scheduler::pcb::PCBState;
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    pub sender: usize,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mailbox {
+    pub messages: VecDeque<Message>,
+    pub waitlist: Vec<Message>,
+    pub receiving: bool,
+    pub last_received: Option<Message>,
+}
+
+impl Mailbox {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            messages: VecDeque::new(),
+            waitlist: Vec::new(),
+            receiving: false,
+            last_received: None,
+        }
+    }
+}
+
+impl Scheduler {
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB
+    pub fn send(&mut self, pid: i32, value: i32) -> Option<usize> {
+        let pid = usize::try_from(pid).ok()?;
+        let capacity = self.config.mailbox_capacity;
+        let message = Message {
+            sender: self.running_pid,
+            value,
+        };
+
+        // Bounds Check
+        if pid >= self.pcb_list.len() {
+            self.refuse(Refusal::UnknownId, "SEND: PID Out Of Bounds");
+            return None;
+        }
+
+        let Some(pcb) = self.pcb_list[pid].as_mut() else {
+            self.refuse(Refusal::UnknownId, "SEND: PID Does Not Exist");
+            return None;
+        };
+
+        if pcb.state == PCBState::ZOMBIE {
+            self.refuse(Refusal::InvalidState, "SEND: Process Has Exited");
+            return None;
+        }
+
+        let mailbox = &mut pcb.mailbox;
+
+        if mailbox.receiving {
+            // Deliver Straight To The Blocked Receiver
+            mailbox.receiving = false;
+            mailbox.last_received = Some(message);
+            self.unblock(pid);
+        } else if capacity.is_some_and(|capacity| mailbox.messages.len() >= capacity) {
+            // Process 0 Can't Block
+            if self.running_pid == 0 {
+                self.refuse(
+                    Refusal::NotPermitted,
+                    "SEND: Process 0 Can't Block On A Full Mailbox",
+                );
+                return None;
+            }
+
+            mailbox.waitlist.push(message);
+            self.block_running();
+        } else {
+            mailbox.messages.push_back(message);
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB
+    pub fn receive(&mut self) -> Option<usize> {
+        let mailbox = &mut self.pcb_list[self.running_pid]
+            .as_mut()
+            .expect("RECEIVE: Running PCB should exist.")
+            .mailbox;
+
+        if let Some(message) = mailbox.messages.pop_front() {
+            mailbox.last_received = Some(message);
+
+            // Make Room For The First Blocked Sender
+            if !mailbox.waitlist.is_empty() {
+                let message = mailbox.waitlist.remove(0);
+                mailbox.messages.push_back(message);
+                self.unblock(message.sender);
+            }
+        } else {
+            // Process 0 Can't Block
+            if self.running_pid == 0 {
+                self.refuse(
+                    Refusal::NotPermitted,
+                    "RECEIVE: Process 0 Can't Block On An Empty Mailbox",
+                );
+                return None;
+            }
+
+            mailbox.receiving = true;
+            self.block_running();
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// Drops the members' pending messages in one sweep, then unblocks whoever was sending to them
+    pub(super) fn drop_mailbox_waiters(&mut self, member: &[bool]) {
+        // Stop Waiting On Other Mailboxes
+        self.pcb_list.iter_mut().flatten().for_each(|pcb| {
+            pcb.mailbox
+                .waitlist
+                .retain(|message| !member[message.sender]);
+        });
+
+        // Senders Blocked On These Mailboxes Give Up
+        let senders: Vec<usize> = self
+            .pcb_list
+            .iter_mut()
+            .enumerate()
+            .filter(|&(pid, _)| member[pid])
+            .filter_map(|(_, pcb)| pcb.as_mut())
+            .flat_map(|pcb| pcb.mailbox.waitlist.drain(..).map(|x| x.sender))
+            .collect();
+
+        for sender in senders {
+            self.unblock(sender);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    pub migrations: usize,
+    pub deadline_misses: usize,
+    /// Fraction of clock ticks each core spent running a process other than process 0
+    pub utilization: Vec<f64>,
+}
+
+impl Scheduler {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            migrations: self.cpu_list.iter().map(|cpu| cpu.migrations).sum(),
+            deadline_misses: self.deadline_misses.len(),
+            utilization: self
+                .cpu_list
+                .iter()
+                .map(|cpu| {
+                    let ticks = cpu.busy_ticks + cpu.idle_ticks;
+
+                    if ticks == 0 {
+                        0.0
+                    } else {
+                        cpu.busy_ticks as f64 / ticks as f64
+                    }
+                })
+                .collect(),
+        }
+    }
+}
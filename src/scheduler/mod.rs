@@ -1,19 +1,69 @@
-use crate::scheduler::defaults::{pcb_list_default, rcb_list_default};
+use std::collections::BTreeSet;
+
+use crate::scheduler::config::{BalanceStrategy, Config, ExitPolicy, QueueMode, SchedulingPolicy};
+use crate::scheduler::cpu::Cpu;
+use crate::scheduler::deadlock::RecoveryAction;
+use crate::scheduler::defaults::{
+    device_list_default, mutex_list_default, pcb_list_with, rcb_list_with, ready_list_with,
+    semaphore_list_default,
+};
+use crate::scheduler::device::Device;
 use crate::scheduler::pcb::PCB;
 use crate::scheduler::rcb::RCB;
+use crate::scheduler::ready::ReadyList;
+use crate::scheduler::realtime::{DeadlineMiss, PeriodicTask};
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::sync::{Mutex, Semaphore};
 
-use self::pcb::{PCBResource, PCBState};
+use self::pcb::{CreateOptions, ExitStatus, PCBResource, PCBState};
 use self::rcb::RCBResource;
 
+pub mod burst;
+pub mod cfs;
+pub mod command;
+pub mod config;
+pub mod cpu;
+pub mod deadlock;
 pub mod defaults;
+pub mod device;
+pub mod group;
+pub mod mailbox;
+pub mod metrics;
 pub mod pcb;
+pub mod ps;
+pub mod query;
 pub mod rcb;
+pub mod ready;
+pub mod realtime;
+pub mod refusal;
+pub mod sync;
+pub mod timer;
 
+#[derive(Clone)]
 pub struct Scheduler {
     pub running_pid: usize,
-    pub pcb_list: [Option<PCB>; 16],
-    pub rcb_list: [RCB; 4],
-    pub ready_list: [Vec<usize>; 3],
+    pub current_core: usize,
+    pub cpu_list: Vec<Cpu>,
+    pub pcb_list: Vec<Option<PCB>>,
+    /// Empty PCB slots, the lowest is used first
+    pub free_pids: BTreeSet<usize>,
+    pub rcb_list: Vec<RCB>,
+    pub ready_list: ReadyList,
+    /// Ready processes by `(vruntime, pid)` under the completely fair policy
+    pub cfs_queue: BTreeSet<(usize, usize)>,
+    pub min_vruntime: usize,
+    pub mutex_list: [Mutex; 4],
+    pub semaphore_list: [Semaphore; 4],
+    pub device_list: [Device; 4],
+    pub clock: usize,
+    /// Every deadlock recovery action, oldest first
+    pub recovery_log: Vec<RecoveryAction>,
+    /// Blocked requests with a timeout by `(expiry tick, pid)`, earliest first
+    pub timers: BTreeSet<(usize, usize)>,
+    pub deadline_misses: Vec<DeadlineMiss>,
+    /// Why the last command run through `apply` was refused, see `last_refusal`
+    refusal: Option<Refusal>,
+    pub config: Config,
 }
 
 impl Default for Scheduler {
@@ -25,254 +75,644 @@ impl Default for Scheduler {
 impl Scheduler {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    #[must_use]
+    pub fn with_config(config: Config) -> Self {
+        let processes = config.processes.max(1);
+
         Self {
             running_pid: 0,
-            pcb_list: pcb_list_default(),
-            rcb_list: rcb_list_default(),
-            ready_list: [vec![0], Vec::new(), Vec::new()],
+            current_core: 0,
+            cpu_list: vec![Cpu::new(); config.cores.max(1)],
+            pcb_list: pcb_list_with(processes),
+            free_pids: (1..processes).collect(),
+            rcb_list: rcb_list_with(&config.resources),
+            ready_list: ready_list_with(processes),
+            cfs_queue: BTreeSet::new(),
+            min_vruntime: 0,
+            mutex_list: mutex_list_default(),
+            semaphore_list: semaphore_list_default(),
+            device_list: device_list_default(),
+            clock: 0,
+            timers: BTreeSet::new(),
+            recovery_log: Vec::new(),
+            refusal: None,
+            deadline_misses: Vec::new(),
+            config,
         }
     }
 
     pub fn init(&mut self) -> Option<usize> {
         self.running_pid = 0;
-        self.pcb_list = pcb_list_default();
-        self.rcb_list = rcb_list_default();
-        self.ready_list = [vec![0], Vec::new(), Vec::new()];
+        self.current_core = 0;
+        self.cpu_list = vec![Cpu::new(); self.config.cores.max(1)];
+        let processes = self.config.processes.max(1);
+        self.pcb_list = pcb_list_with(processes);
+        self.free_pids = (1..processes).collect();
+        self.rcb_list = rcb_list_with(&self.config.resources);
+        self.ready_list = ready_list_with(processes);
+        self.cfs_queue = BTreeSet::new();
+        self.min_vruntime = 0;
+        self.mutex_list = mutex_list_default();
+        self.semaphore_list = semaphore_list_default();
+        self.device_list = device_list_default();
+        self.clock = 0;
+        self.timers = BTreeSet::new();
+        self.recovery_log = Vec::new();
+        self.refusal = None;
+        self.deadline_misses = Vec::new();
 
         Some(self.running_pid)
     }
 
     fn scheduler(&mut self) -> usize {
-        self.running_pid = *self
-            .ready_list
-            .iter()
-            .rev()
-            .find(|list| !list.is_empty())
-            .expect("SCHEDULER: Ready List Shouldn't Be Empty")
-            .first()
-            .expect("SCHEDULER: Error Accessing First Element");
+        self.dispatch();
+        self.running_pid = self.cpu_list[self.current_core].running_pid;
 
         self.running_pid
     }
 
     pub fn create(&mut self, priority: i32) -> Option<usize> {
+        self.create_with(priority, &CreateOptions::default())
+    }
+
+    /// Fills a free PID with a child of the running process, which inherits its group,
+    /// and queues it without rescheduling
+    fn spawn(
+        &mut self,
+        pid: usize,
+        priority: usize,
+        options: &CreateOptions,
+        task: Option<PeriodicTask>,
+    ) {
+        // Create PCB
+        let mut pcb = PCB::new(priority, Some(self.running_pid));
+        pcb.name.clone_from(&options.name);
+        pcb.group = self.pcb_list[self.running_pid]
+            .as_ref()
+            .expect("Running PCB should exist.")
+            .group;
+        pcb.affinity = options.affinity;
+        pcb.core = self.place(options.affinity);
+        pcb.vruntime = self.min_vruntime;
+        pcb.burst.estimate = options.burst;
+        pcb.task = task;
+        self.pcb_list[pid] = Some(pcb);
+
+        // Add To Parent's Children List
+        self.pcb_list[self.running_pid]
+            .as_mut()
+            .expect("Running PCB should exist.")
+            .children
+            .push(pid);
+
+        // Add To Ready List
+        self.enqueue(pid);
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB
+    pub fn create_with(&mut self, priority: i32, options: &CreateOptions) -> Option<usize> {
         let priority = usize::try_from(priority).ok()?;
 
         // Bounds check
         if priority >= self.ready_list.len() {
-            eprintln!("Priority Out Of Bounds");
+            self.refuse(Refusal::InvalidArgument, "Priority Out Of Bounds");
             return None;
         }
 
-        // Find an empty PCB
-        let empty_pid = match self.pcb_list.iter().position(std::option::Option::is_none) {
-            Some(pos) => pos,
-            None => {
-                eprintln!("No Empty PCBs");
+        if options
+            .affinity
+            .is_some_and(|core| core >= self.cpu_list.len())
+        {
+            self.refuse(Refusal::UnknownId, "Affinity Core Out Of Bounds");
+            return None;
+        }
+
+        if let Some(name) = &options.name {
+            // Numeric Names Would Be Mistaken For PIDs
+            if name.is_empty() || name.parse::<i64>().is_ok() {
+                self.refuse(Refusal::InvalidArgument, "Invalid Process Name");
                 return None;
             }
-        };
 
-        // Create PCB
-        self.pcb_list[empty_pid] = Some(PCB::new(priority, Some(self.running_pid)));
+            if self.pid_of(name).is_some() {
+                self.refuse(Refusal::InvalidState, "Process Name Already In Use");
+                return None;
+            }
+        }
 
-        // Add To Parent's Children List
-        self.pcb_list[self.running_pid]
-            .as_mut()
-            .expect("Running PCB should exist.")
-            .children
-            .push(empty_pid);
+        // Find an empty PCB
+        let Some(empty_pid) = self.free_pids.pop_first() else {
+            self.refuse(Refusal::InvalidState, "No Empty PCBs");
+            return None;
+        };
 
-        // Add To Ready List
-        self.ready_list[priority].push(empty_pid);
+        self.spawn(empty_pid, priority, options, None);
 
         Some(self.scheduler())
     }
 
     fn is_child_of_current_process(&self, pid: usize) -> bool {
-        // Bounds check
-        if pid >= self.pcb_list.len() {
-            return false;
-        }
+        // Walk Up The Parent Chain
+        let mut current = Some(pid);
+        while let Some(pid) = current {
+            if pid == self.running_pid {
+                return true;
+            }
 
-        // Check if the current process is the child process
-        if pid == self.running_pid {
-            return true;
+            current = self
+                .pcb_list
+                .get(pid)
+                .and_then(Option::as_ref)
+                .and_then(|pcb| pcb.parent);
         }
 
-        match &self.pcb_list[pid] {
-            Some(pcb) => match pcb.parent {
-                Some(parent_id) => {
-                    if parent_id == self.running_pid {
-                        true
-                    } else {
-                        self.is_child_of_current_process(parent_id)
-                    }
-                }
-                None => false,
-            },
-            None => false,
-        }
+        false
     }
 
     pub fn destroy(&mut self, pid: i32) -> Option<usize> {
+        self.destroy_tree(pid).map(|_| self.running_pid)
+    }
+
+    /// Destroys the process and all of its descendants, rescheduling once at the end.
+    /// Returns the destroyed PIDs, parents before their children.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the parent and children links of the process tree are inconsistent
+    pub fn destroy_tree(&mut self, pid: i32) -> Option<Vec<usize>> {
         let pid = usize::try_from(pid).ok()?;
 
         // Bounds Check
         if pid >= self.pcb_list.len() {
-            eprintln!("DESTROY: PID Out Of Bounds");
+            self.refuse(Refusal::UnknownId, "DESTROY: PID Out Of Bounds");
             return None;
         }
 
         // Don't Destroy Process 0
         if pid == 0 {
-            eprintln!("DESTROY: Cannot Destroy Process 0");
+            self.refuse(Refusal::NotPermitted, "DESTROY: Cannot Destroy Process 0");
+            return None;
+        }
+
+        if self.pcb_list[pid].is_none() {
+            self.refuse(Refusal::UnknownId, "DESTROY: PID Does Not Exist");
             return None;
         }
 
         // Only Destroy Child Processes
         if !self.is_child_of_current_process(pid) {
-            eprintln!("DESTROY: PID Is Not A Child Of The Current Process");
+            self.refuse(
+                Refusal::NotPermitted,
+                "DESTROY: PID Is Not A Child Of The Current Process",
+            );
             return None;
         }
 
-        // Recursively Destroy Children
-        let children = match &self.pcb_list[pid] {
-            Some(pcb) => pcb.children.clone(),
-            None => {
-                eprintln!("DESTROY: PID Does Not Exist");
-                return None;
-            }
-        };
+        let destroyed = self.tear_down(pid);
+        self.scheduler();
 
-        for &child in &children {
-            self.destroy(child.try_into().unwrap());
+        Some(destroyed)
+    }
+
+    /// The process and its descendants, parents before their children
+    fn subtree(&self, root: usize) -> Vec<usize> {
+        let mut subtree = Vec::new();
+        let mut stack = vec![root];
+
+        while let Some(pid) = stack.pop() {
+            subtree.push(pid);
+
+            let pcb = self.pcb_list[pid]
+                .as_ref()
+                .expect("DESTROY: Child PCB should exist.");
+            stack.extend(pcb.children.iter().rev());
         }
 
-        // Get the PCB of the process to be destroyed
-        let pcb = match &self.pcb_list[pid] {
-            Some(pcb) => pcb,
-            None => {
-                eprintln!("DESTROY: PID Does Not Exist");
-                return None;
+        subtree
+    }
+
+    /// Removes a whole subtree in one pass without rescheduling
+    fn tear_down(&mut self, root: usize) -> Vec<usize> {
+        let subtree = self.subtree(root);
+
+        // Remove The Root From Its Parent's Children List, The Descendants Go With It
+        let parent = self.pcb_list[root]
+            .as_ref()
+            .and_then(|pcb| pcb.parent)
+            .expect("DESTROY: All processes should have a parent except process 0.");
+        let parent_pcb = self.pcb_list[parent]
+            .as_mut()
+            .expect("DESTROY: Parent PCB should exist.");
+        let pos = parent_pcb
+            .children
+            .iter()
+            .position(|&x| x == root)
+            .expect("DESTROY: Child should be in parent's children list.");
+        parent_pcb.children.remove(pos);
+
+        // A Parent Waiting On This Process Has Nothing Left To Wait For
+        self.wake_waiting_parent(parent, root);
+
+        // Leave The Ready List First
+        let mut member = vec![false; self.pcb_list.len()];
+        let mut blocked = false;
+        for &pid in &subtree {
+            self.dequeue(pid);
+            member[pid] = true;
+
+            // Only Blocked Processes Sit In Waitlists
+            blocked |= self.pcb_list[pid].as_ref().is_some_and(|pcb| {
+                matches!(pcb.state, PCBState::BLOCKED | PCBState::SUSPENDED_BLOCKED)
+            });
+        }
+
+        // Then Every Waitlist In One Sweep, So Released Units Aren't Granted Back
+        if blocked {
+            self.rcb_list.iter_mut().for_each(|rcb| {
+                rcb.waitlist.retain(|x| !member[x.pid]);
+            });
+            self.timers.retain(|&(_, pid)| !member[pid]);
+            self.mutex_list.iter_mut().for_each(|mutex| {
+                mutex.waitlist.retain(|&x| !member[x]);
+            });
+            self.semaphore_list.iter_mut().for_each(|semaphore| {
+                semaphore.waitlist.retain(|&x| !member[x]);
+            });
+            self.device_list.iter_mut().for_each(|device| {
+                device.queue.retain(|x| !member[x.pid]);
+            });
+        }
+
+        // Release Everything The Subtree Holds
+        for &pid in subtree.iter().rev() {
+            let resources = self.pcb_list[pid]
+                .as_ref()
+                .expect("DESTROY: Current PCB should exist.")
+                .resources
+                .clone();
+
+            for resource in &resources {
+                self.release_units(pid, resource.rid, resource.units)
+                    .expect("DESTROY: Held resources should be releasable.");
             }
-        };
 
-        // Remove From The Ready List
-        if let Some(pos) = self.ready_list[pcb.priority].iter().position(|&x| x == pid) {
-            self.ready_list[pcb.priority].remove(pos);
+            self.release_mutexes(pid);
         }
 
-        // Remove From The Parent's Children List
-        match pcb.parent {
-            Some(parent) => {
-                let parent_pcb = self.pcb_list[parent]
-                    .as_mut()
-                    .expect("DESTROY: Parent PCB should exist.");
+        self.drop_mailbox_waiters(&member);
 
-                match parent_pcb.children.iter().position(|&x| x == pid) {
-                    Some(pos) => {
-                        parent_pcb.children.remove(pos);
-                    }
-                    None => {
-                        panic!("DESTROY: Child should be in parent's children list.");
-                    }
+        // Remove From The PCB List, Dropping Any Member Woken Above
+        for &pid in &subtree {
+            self.dequeue(pid);
+            self.free_slot(pid);
+        }
+
+        subtree
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the running process or its parent has no PCB
+    pub fn exit(&mut self, code: i32) -> Option<usize> {
+        let pid = self.running_pid;
+
+        // Process 0 Can't Exit
+        if pid == 0 {
+            self.refuse(Refusal::NotPermitted, "EXIT: Process 0 Cannot Exit");
+            return None;
+        }
+
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("EXIT: Running PCB should exist.");
+
+        match self.config.exit_policy {
+            ExitPolicy::Cascade => {
+                for child in pcb.children.clone() {
+                    self.tear_down(child);
                 }
             }
-            None => {
-                panic!("DESTROY: All processes should have a parent except process 0.");
+            ExitPolicy::ReparentToInit | ExitPolicy::ReparentToGrandparent => {
+                let new_parent = if self.config.exit_policy == ExitPolicy::ReparentToInit {
+                    0
+                } else {
+                    pcb.parent
+                        .expect("EXIT: Running process should have a parent.")
+                };
+                let children = std::mem::take(&mut pcb.children);
+
+                for &child in &children {
+                    self.pcb_list[child]
+                        .as_mut()
+                        .expect("EXIT: Child PCB should exist.")
+                        .parent = Some(new_parent);
+                }
+
+                self.pcb_list[new_parent]
+                    .as_mut()
+                    .expect("EXIT: New parent PCB should exist.")
+                    .children
+                    .extend(children);
             }
         }
 
+        // Remove From The Ready List
+        self.dequeue(pid);
+        self.end_burst(pid);
+
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("EXIT: Running PCB should exist.");
+
+        // Become A Zombie Until The Parent Collects The Exit Code
+        pcb.state = PCBState::ZOMBIE;
+        pcb.exit_code = Some(code);
+        let parent = pcb
+            .parent
+            .expect("EXIT: Running process should have a parent.");
+
         // Release Resources
-        let pcb_2 = self.pcb_list[pid]
+        for resource in pcb.resources.clone() {
+            self.release_units(pid, resource.rid, resource.units)
+                .expect("EXIT: Held resources should be releasable.");
+        }
+
+        self.release_mutexes(pid);
+        let mut member = vec![false; self.pcb_list.len()];
+        member[pid] = true;
+        self.drop_mailbox_waiters(&member);
+
+        if self.wake_waiting_parent(parent, pid) {
+            self.reap(pid);
+        }
+
+        Some(self.scheduler())
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB or is missing from the ready list
+    pub fn wait(&mut self, pid: i32) -> Option<usize> {
+        let pid = usize::try_from(pid).ok()?;
+
+        // Bounds Check
+        if pid >= self.pcb_list.len() {
+            self.refuse(Refusal::UnknownId, "WAIT: PID Out Of Bounds");
+            return None;
+        }
+
+        let Some(child) = &self.pcb_list[pid] else {
+            self.refuse(Refusal::UnknownId, "WAIT: PID Does Not Exist");
+            return None;
+        };
+
+        // Only Wait On Direct Children
+        if child.parent != Some(self.running_pid) {
+            self.refuse(
+                Refusal::NotPermitted,
+                "WAIT: PID Is Not A Child Of The Current Process",
+            );
+            return None;
+        }
+
+        // Collect An Exited Child Immediately
+        if child.state == PCBState::ZOMBIE {
+            self.reap(pid);
+            return Some(self.scheduler());
+        }
+
+        // Process 0 Can't Block
+        if self.running_pid == 0 {
+            self.refuse(
+                Refusal::NotPermitted,
+                "WAIT: Process 0 Can't Wait On A Running Child",
+            );
+            return None;
+        }
+
+        // BLOCK
+        self.pcb_list[self.running_pid]
             .as_mut()
-            .expect("DESTROY: Current PCB should exist.")
-            .resources
-            .clone();
+            .expect("WAIT: Current PCB should exist.")
+            .waiting_on = Some(pid);
+        self.block_running();
+
+        Some(self.scheduler())
+    }
+
+    fn wake_waiting_parent(&mut self, parent: usize, pid: usize) -> bool {
+        let parent_pcb = self.pcb_list[parent]
+            .as_mut()
+            .expect("Parent PCB should exist.");
+
+        if parent_pcb.waiting_on != Some(pid) {
+            return false;
+        }
+
+        parent_pcb.waiting_on = None;
+        self.unblock(parent);
+
+        true
+    }
+
+    fn block_running(&mut self) {
+        self.block(self.running_pid);
+    }
+
+    fn block(&mut self, pid: usize) {
+        self.end_burst(pid);
+        self.pcb_list[pid]
+            .as_mut()
+            .expect("BLOCK: PCB should exist.")
+            .state = PCBState::BLOCKED;
+
+        assert!(
+            self.dequeue(pid),
+            "BLOCK: Process should be in the ready list."
+        );
+    }
+
+    fn unblock(&mut self, pid: usize) {
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("UNBLOCK: PCB should exist.");
 
-        for resource in &pcb_2 {
-            self.release_helper(pid, resource.rid, resource.units)
-                .unwrap();
+        // A Suspended Process Stays Off The Ready List Until Resumed
+        if pcb.state == PCBState::SUSPENDED_BLOCKED {
+            pcb.state = PCBState::SUSPENDED_READY;
+        } else {
+            pcb.state = PCBState::READY;
+            self.place_woken(pid);
+            self.enqueue(pid);
         }
+    }
+
+    fn enqueue(&mut self, pid: usize) {
+        let pcb = self.pcb_list[pid]
+            .as_ref()
+            .expect("ENQUEUE: PCB should exist.");
+
+        self.ready_list.push_back(pcb.priority, pid);
+
+        if self.config.policy == SchedulingPolicy::CompletelyFair && pid != 0 {
+            self.cfs_queue.insert((pcb.vruntime, pid));
+        }
+    }
+
+    /// Returns whether the process was in the ready list
+    fn dequeue(&mut self, pid: usize) -> bool {
+        let pcb = self.pcb_list[pid]
+            .as_ref()
+            .expect("DEQUEUE: PCB should exist.");
 
-        self.rcb_list.iter_mut().for_each(|rcb| {
-            rcb.waitlist.retain(|x| x.pid != pid);
-        });
+        self.cfs_queue.remove(&(pcb.vruntime, pid));
 
-        // Remove From The PCB List
+        self.ready_list.remove(pid)
+    }
+
+    /// PID the next created process will get
+    #[must_use]
+    pub fn next_pid(&self) -> Option<usize> {
+        self.free_pids.first().copied()
+    }
+
+    /// PID of the live process with this name
+    #[must_use]
+    pub fn pid_of(&self, name: &str) -> Option<usize> {
+        self.pcb_list.iter().position(|pcb| {
+            pcb.as_ref()
+                .is_some_and(|pcb| pcb.name.as_deref() == Some(name))
+        })
+    }
+
+    /// RID of the resource with this name
+    #[must_use]
+    pub fn rid_of(&self, name: &str) -> Option<usize> {
+        self.rcb_list
+            .iter()
+            .position(|rcb| rcb.name.as_deref() == Some(name))
+    }
+
+    fn free_slot(&mut self, pid: usize) {
         self.pcb_list[pid] = None;
+        self.free_pids.insert(pid);
+    }
 
-        Some(self.scheduler())
+    fn reap(&mut self, pid: usize) {
+        let zombie = self.pcb_list[pid]
+            .as_ref()
+            .expect("REAP: Zombie PCB should exist.");
+        let parent = zombie.parent.expect("REAP: Zombie should have a parent.");
+        let code = zombie
+            .exit_code
+            .expect("REAP: Zombie should have an exit code.");
+
+        let parent_pcb = self.pcb_list[parent]
+            .as_mut()
+            .expect("REAP: Parent PCB should exist.");
+        parent_pcb.children.retain(|&x| x != pid);
+
+        // Hand The Exit Code To The Parent
+        parent_pcb.collected = Some(ExitStatus { pid, code });
+
+        // Free The Slot For Reuse
+        self.free_slot(pid);
     }
 
     pub fn request(&mut self, rid: i32, units: i32) -> Option<usize> {
+        self.request_within(rid, units, None)
+    }
+
+    /// Fails instead of blocking when the units aren't available
+    pub fn try_request(&mut self, rid: i32, units: i32) -> Option<usize> {
+        self.request_within(rid, units, Some(0))
+    }
+
+    /// Blocks for at most `timeout` clock ticks. On expiry the process is made ready again
+    /// with the RID in its `failed_request`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB or is missing from the ready list
+    pub fn request_within(&mut self, rid: i32, units: i32, timeout: Option<i32>) -> Option<usize> {
         let rid = usize::try_from(rid).ok()?;
         let units = usize::try_from(units).ok()?;
+        let timeout = match timeout {
+            Some(timeout) => Some(usize::try_from(timeout).ok()?),
+            None => None,
+        };
 
         // Bounds Check
         if rid >= self.rcb_list.len() {
-            eprintln!("REQUEST: RID Out Of Bounds");
+            self.refuse(Refusal::UnknownId, "REQUEST: RID Out Of Bounds");
             return None;
         }
 
         if units == 0 {
-            eprintln!("REQUEST: Units Cannot Be 0");
+            self.refuse(Refusal::InvalidArgument, "REQUEST: Units Cannot Be 0");
             return None;
         }
 
         // Process 0 Can't Request
         if self.running_pid == 0 {
-            eprintln!("REQUEST: Process 0 Can't Request Resources");
+            self.refuse(
+                Refusal::NotPermitted,
+                "REQUEST: Process 0 Can't Request Resources",
+            );
             return None;
         }
 
-        let pcb = match &mut self.pcb_list[self.running_pid] {
-            Some(pcb) => pcb,
-            None => {
-                panic!("REQUEST: Current PCB should exist.");
-            }
-        };
-        let rcb = match self.rcb_list.get_mut(rid) {
-            Some(rcb) => rcb,
-            None => {
-                eprintln!("REQUEST: RCB Does Not Exist");
-                return None;
-            }
+        let pcb = self.pcb_list[self.running_pid]
+            .as_mut()
+            .expect("REQUEST: Current PCB should exist.");
+        pcb.failed_request = None;
+        let Some(rcb) = self.rcb_list.get_mut(rid) else {
+            self.refuse(Refusal::UnknownId, "REQUEST: RCB Does Not Exist");
+            return None;
         };
 
         let units_held_pos = pcb.resources.iter().position(|x| x.rid == rid);
         let units_held = units_held_pos.map_or(0, |pos| pcb.resources[pos].units);
 
         if rcb.inventory < units + units_held {
-            eprintln!("REQUEST: Units Exceeds Max Inventory");
+            self.refuse(
+                Refusal::InvalidArgument,
+                "REQUEST: Units Exceeds Max Inventory",
+            );
             return None;
         }
 
         if rcb.units_available < units {
-            // BLOCK
-
-            // Update PCB State To Blocked
-            pcb.state = PCBState::BLOCKED;
-
-            // Remove From Ready List
-            match self.ready_list[pcb.priority]
-                .iter()
-                .position(|&x| x == self.running_pid)
-            {
-                Some(pos) => {
-                    self.ready_list[pcb.priority].remove(pos);
-                }
-                None => {
-                    panic!("REQUEST: Current process should be in the ready list.");
-                }
+            if timeout == Some(0) {
+                self.refuse(Refusal::InvalidState, "REQUEST: Units Not Available");
+                return None;
             }
 
+            // BLOCK
+
             // Add To RCB Waitlist
-            rcb.waitlist.push(RCBResource {
+            let expires = timeout.map(|timeout| self.clock + timeout);
+            rcb.waitlist.push_back(RCBResource {
                 pid: self.running_pid,
                 units,
+                expires,
             });
+            if let Some(expires) = expires {
+                self.timers.insert((expires, self.running_pid));
+            }
+
+            // Update PCB State To Blocked And Remove From Ready List
+            self.block_running();
+
+            // Only A Blocking Request Can Close A Cycle
+            if let Some(strategy) = self.config.deadlock_recovery {
+                self.recover(strategy);
+            }
 
             return Some(self.scheduler());
         }
@@ -292,7 +732,8 @@ impl Scheduler {
         Some(self.scheduler())
     }
 
-    fn release_helper(&mut self, pid: usize, rid: usize, units: usize) -> Option<usize> {
+    /// Gives units back and grants waiters that now fit, without rescheduling
+    fn release_units(&mut self, pid: usize, rid: usize, units: usize) -> Option<()> {
         let pcb = self.pcb_list.get_mut(pid)?.as_mut()?;
 
         let rcb = self.rcb_list.get_mut(rid)?;
@@ -310,6 +751,16 @@ impl Scheduler {
 
         rcb.units_available += units;
 
+        self.grant_waiters(rid);
+
+        Some(())
+    }
+
+    /// Grants queued requests that fit in the available units, in waitlist order
+    fn grant_waiters(&mut self, rid: usize) {
+        let rcb = &mut self.rcb_list[rid];
+
+        let mut granted = Vec::new();
         let mut i = 0;
         while i < rcb.waitlist.len() && rcb.units_available > 0 {
             if rcb.waitlist[i].units <= rcb.units_available {
@@ -337,18 +788,20 @@ impl Scheduler {
                     }
                 }
 
-                temp_pcb.state = PCBState::READY;
-
                 rcb.units_available -= temp_units;
+                if let Some(expires) = rcb.waitlist[i].expires {
+                    self.timers.remove(&(expires, temp_pid));
+                }
                 rcb.waitlist.remove(i);
-
-                self.ready_list[temp_pcb.priority].push(temp_pid);
+                granted.push(temp_pid);
             } else {
                 i += 1;
             }
         }
 
-        Some(self.scheduler())
+        for temp_pid in granted {
+            self.unblock(temp_pid);
+        }
     }
 
     pub fn release(&mut self, rid: i32, units: i32) -> Option<usize> {
@@ -357,45 +810,216 @@ impl Scheduler {
 
         // Bounds Check
         if rid >= self.rcb_list.len() {
-            eprintln!("RELEASE: RID Out Of Bounds");
+            self.refuse(Refusal::UnknownId, "RELEASE: RID Out Of Bounds");
             return None;
         }
 
         if units == 0 {
-            eprintln!("RELEASE: Units Cannot Be 0");
+            self.refuse(Refusal::InvalidArgument, "RELEASE: Units Cannot Be 0");
+            return None;
+        }
+
+        self.release_units(self.running_pid, rid, units)?;
+
+        Some(self.scheduler())
+    }
+
+    /// Takes a ready process off the ready list, a blocked one keeps its place in its waitlist
+    fn suspend_one(&mut self, pid: usize) -> Result<(), Refusal> {
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("SUSPEND: PCB should exist.");
+
+        match pcb.state {
+            PCBState::READY => {
+                pcb.state = PCBState::SUSPENDED_READY;
+
+                // Remove From The Ready List
+                self.dequeue(pid);
+            }
+            // Keep Its Place In The RCB Waitlist
+            PCBState::BLOCKED => pcb.state = PCBState::SUSPENDED_BLOCKED,
+            PCBState::SUSPENDED_READY | PCBState::SUSPENDED_BLOCKED | PCBState::ZOMBIE => {
+                return Err(Refusal::InvalidState);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Puts a suspended ready process back on the ready list, a blocked one goes on waiting
+    fn resume_one(&mut self, pid: usize) -> Result<(), Refusal> {
+        let pcb = self.pcb_list[pid]
+            .as_mut()
+            .expect("RESUME: PCB should exist.");
+
+        match pcb.state {
+            PCBState::SUSPENDED_READY => {
+                pcb.state = PCBState::READY;
+                self.place_woken(pid);
+                self.enqueue(pid);
+            }
+            PCBState::SUSPENDED_BLOCKED => pcb.state = PCBState::BLOCKED,
+            PCBState::READY | PCBState::BLOCKED | PCBState::ZOMBIE => {
+                return Err(Refusal::InvalidState);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the process has no PCB
+    pub fn suspend(&mut self, pid: i32) -> Option<usize> {
+        let pid = usize::try_from(pid).ok()?;
+
+        // Bounds Check
+        if pid >= self.pcb_list.len() {
+            self.refuse(Refusal::UnknownId, "SUSPEND: PID Out Of Bounds");
             return None;
         }
 
-        self.release_helper(self.running_pid, rid, units)
+        // Don't Suspend Process 0
+        if pid == 0 {
+            self.refuse(Refusal::NotPermitted, "SUSPEND: Cannot Suspend Process 0");
+            return None;
+        }
+
+        if self.pcb_list[pid].is_none() {
+            self.refuse(Refusal::UnknownId, "SUSPEND: PID Does Not Exist");
+            return None;
+        }
+
+        // Only Suspend Child Processes
+        if !self.is_child_of_current_process(pid) {
+            self.refuse(
+                Refusal::NotPermitted,
+                "SUSPEND: PID Is Not A Child Of The Current Process",
+            );
+            return None;
+        }
+
+        if let Err(refusal) = self.suspend_one(pid) {
+            self.refuse(
+                refusal,
+                "SUSPEND: Process Is Already Suspended Or Has Exited",
+            );
+            return None;
+        }
+
+        Some(self.scheduler())
     }
 
     /// # Panics
     ///
-    /// Will panic if the scheduler data is corrupted from bug
+    /// Will panic if the process has no PCB
+    pub fn resume(&mut self, pid: i32) -> Option<usize> {
+        let pid = usize::try_from(pid).ok()?;
+
+        // Bounds Check
+        if pid >= self.pcb_list.len() {
+            self.refuse(Refusal::UnknownId, "RESUME: PID Out Of Bounds");
+            return None;
+        }
+
+        if self.pcb_list[pid].is_none() {
+            self.refuse(Refusal::UnknownId, "RESUME: PID Does Not Exist");
+            return None;
+        }
+
+        // Only Resume Child Processes
+        if !self.is_child_of_current_process(pid) {
+            self.refuse(
+                Refusal::NotPermitted,
+                "RESUME: PID Is Not A Child Of The Current Process",
+            );
+            return None;
+        }
+
+        if let Err(refusal) = self.resume_one(pid) {
+            self.refuse(refusal, "RESUME: Process Is Not Suspended");
+            return None;
+        }
+
+        Some(self.scheduler())
+    }
+
+    fn tick(&mut self) {
+        // Every Core Ran Its Process For This Tick
+        for core in 0..self.cpu_list.len() {
+            let pid = self.cpu_list[core].running_pid;
+            self.charge(pid);
+            self.charge_burst(pid);
+        }
+
+        self.account_cpu_time();
+        self.run_periodic_tasks();
+        self.clock += 1;
+        self.release_periodic_tasks();
+
+        // Completion Interrupts
+        self.complete_io();
+        self.expire_requests();
+
+        if let BalanceStrategy::PushMigration { period } = self.config.balancing {
+            if self.config.queue_mode == QueueMode::PerCore
+                && self.clock.is_multiple_of(period.max(1))
+            {
+                self.push_migrate();
+            }
+        }
+    }
+
     pub fn timeout(&mut self) -> Option<usize> {
-        let priority = self.pcb_list[self.running_pid]
+        Some(self.timeout_core(self.current_core))
+    }
+
+    pub fn timeout_on(&mut self, core: i32) -> Option<usize> {
+        let core = usize::try_from(core).ok()?;
+
+        // Bounds Check
+        if core >= self.cpu_list.len() {
+            self.refuse(Refusal::UnknownId, "TIMEOUT: Core Out Of Bounds");
+            return None;
+        }
+
+        Some(self.timeout_core(core))
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the scheduler data is corrupted from bug
+    fn timeout_core(&mut self, core: usize) -> usize {
+        let pid = self.cpu_list[core].running_pid;
+
+        self.tick();
+
+        let pcb = self.pcb_list[pid]
             .as_ref()
-            .expect("Running PCB should exist")
-            .priority;
+            .expect("Running PCB should exist");
+        let priority = pcb.priority;
 
-        let priority_level_list = self
-            .ready_list
-            .get_mut(priority)
-            .expect("Priority level list should exist");
+        // A Periodic Task That Finished Its Job Has Already Left The Ready List
+        if pcb.state == PCBState::BLOCKED {
+            return self.scheduler();
+        }
 
-        assert!(
-            !priority_level_list.is_empty(),
-            "Priority level list shouldn't be empty. Should contain current process."
-        );
+        // Other Cores Or Policies May Run Processes Ahead Of This One In The Same Level
+        let top_expected =
+            self.cpu_list.len() == 1 && self.config.policy == SchedulingPolicy::Priority;
 
-        assert_eq!(
-            priority_level_list[0], self.running_pid,
+        assert!(
+            !top_expected || self.ready_list.front(priority) == Some(pid),
             "TIMEOUT: Current process should be at the top of the ready list."
         );
 
-        priority_level_list.remove(0);
-        priority_level_list.push(self.running_pid);
+        assert!(
+            self.ready_list.remove(pid),
+            "TIMEOUT: Running process should be in the ready list."
+        );
+        self.ready_list.push_back(priority, pid);
 
-        Some(self.scheduler())
+        self.scheduler()
     }
 }
@@ -1,7 +1,15 @@
-#[derive(Debug, PartialEq, Eq)]
+use crate::scheduler::burst::Burst;
+use crate::scheduler::mailbox::Mailbox;
+use crate::scheduler::realtime::PeriodicTask;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PCBState {
     READY,
     BLOCKED,
+    SUSPENDED_READY,
+    SUSPENDED_BLOCKED,
+    ZOMBIE,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,24 +18,71 @@ pub struct PCBResource {
     pub units: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// Exit code of a child, kept by the parent that collected it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitStatus {
+    pub pid: usize,
+    pub code: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PCB {
+    pub name: Option<String>,
     pub state: PCBState,
     pub priority: usize,
     pub parent: Option<usize>,
+    /// Process group, inherited from the parent
+    pub group: usize,
     pub children: Vec<usize>,
     pub resources: Vec<PCBResource>,
+    pub exit_code: Option<i32>,
+    /// Last child collected by `wait`
+    pub collected: Option<ExitStatus>,
+    pub waiting_on: Option<usize>,
+    /// RID of the last request that timed out, cleared by the next request
+    pub failed_request: Option<usize>,
+    pub mailbox: Mailbox,
+    pub affinity: Option<usize>,
+    pub core: Option<usize>,
+    pub task: Option<PeriodicTask>,
+    pub vruntime: usize,
+    pub burst: Burst,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateOptions {
+    /// Unique among live processes, so commands can refer to the process by it
+    pub name: Option<String>,
+    /// Pins the process to a single core
+    pub affinity: Option<usize>,
+    /// Predicted length of the first CPU burst
+    pub burst: Option<usize>,
 }
 
 impl PCB {
     #[must_use]
-    pub fn new(priority: usize, parent: Option<usize>) -> Self {
+    pub const fn new(priority: usize, parent: Option<usize>) -> Self {
         Self {
+            name: None,
             priority,
             parent,
+            group: 0,
             state: PCBState::READY,
             children: Vec::new(),
             resources: Vec::new(),
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst {
+                estimate: None,
+                elapsed: 0,
+            },
         }
     }
 }
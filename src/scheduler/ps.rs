@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use crate::scheduler::Scheduler;
+
+impl Scheduler {
+    /// Process table with one line per PCB
+    #[must_use]
+    pub fn ps(&self) -> String {
+        let mut table = String::from("PID PPID GRP PRI STATE             EST RUN NAME\n");
+
+        for (pid, pcb) in self.pcb_list.iter().enumerate() {
+            let Some(pcb) = pcb else {
+                continue;
+            };
+
+            let parent = pcb
+                .parent
+                .map_or_else(|| String::from("-"), |x| x.to_string());
+            // Show The Estimate Shortest Job First Schedules By, Which Process 0 Never Needs
+            let estimate = if self.tracks_bursts() && pid != 0 {
+                Some(self.burst_estimate(pid))
+            } else {
+                pcb.burst.estimate
+            }
+            .map_or_else(|| String::from("-"), |x| x.to_string());
+            let state = format!("{:?}", pcb.state);
+            let name = pcb.name.as_deref().unwrap_or("-");
+
+            writeln!(
+                table,
+                "{pid:>3} {parent:>4} {:>3} {:>3} {state:<17} {estimate:>3} {:>3} {name}",
+                pcb.group, pcb.priority, pcb.burst.elapsed
+            )
+            .expect("Writing to a String should not fail");
+        }
+
+        table
+    }
+}
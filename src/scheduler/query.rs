@@ -0,0 +1,183 @@
+use std::collections::VecDeque;
+
+use crate::scheduler::deadlock::RecoveryAction;
+use crate::scheduler::pcb::{PCBState, PCB};
+use crate::scheduler::rcb::{RCBResource, RCB};
+use crate::scheduler::realtime::DeadlineMiss;
+use crate::scheduler::Scheduler;
+
+/// What a blocked process is waiting for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockedOn {
+    /// Units of a resource
+    Resource {
+        rid: usize,
+        units: usize,
+    },
+    Mutex(usize),
+    Semaphore(usize),
+    /// Its request on a device to finish
+    Device(usize),
+    /// The child to exit
+    Child(usize),
+    /// Room in the mailbox of the receiver
+    Send(usize),
+    /// A message in its own mailbox
+    Receive,
+}
+
+/// Read-only views of the scheduler state, so callers don't depend on its fields
+impl Scheduler {
+    #[must_use]
+    pub fn process(&self, pid: usize) -> Option<&PCB> {
+        self.pcb_list.get(pid)?.as_ref()
+    }
+
+    /// Every slot of the process table by PID, `None` where the PID is free
+    pub fn processes(&self) -> impl Iterator<Item = Option<&PCB>> + '_ {
+        self.pcb_list.iter().map(Option::as_ref)
+    }
+
+    #[must_use]
+    pub const fn cores(&self) -> usize {
+        self.cpu_list.len()
+    }
+
+    /// Process 0 while the core is idle
+    #[must_use]
+    pub fn running(&self, core: usize) -> Option<usize> {
+        self.cpu_list.get(core).map(|cpu| cpu.running_pid)
+    }
+
+    #[must_use]
+    pub fn children(&self, pid: usize) -> Option<&[usize]> {
+        self.process(pid).map(|pcb| pcb.children.as_slice())
+    }
+
+    /// Every process below `pid` in the process tree, parents before their children
+    pub fn descendants(&self, pid: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack: Vec<usize> = self
+            .children(pid)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+
+        std::iter::from_fn(move || {
+            let pid = stack.pop()?;
+            stack.extend(self.children(pid).unwrap_or_default().iter().rev());
+
+            Some(pid)
+        })
+    }
+
+    #[must_use]
+    pub fn resource(&self, rid: usize) -> Option<&RCB> {
+        self.rcb_list.get(rid)
+    }
+
+    /// Every resource by RID
+    pub fn resources(&self) -> impl Iterator<Item = &RCB> + '_ {
+        self.rcb_list.iter()
+    }
+
+    /// Processes holding units of the resource, with the units each holds
+    pub fn holders(&self, rid: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.pcb_list
+            .iter()
+            .enumerate()
+            .filter_map(move |(pid, pcb)| {
+                pcb.as_ref()?
+                    .resources
+                    .iter()
+                    .find(|resource| resource.rid == rid)
+                    .map(|resource| (pid, resource.units))
+            })
+    }
+
+    /// Requests blocked on the resource, in the order they are granted
+    #[must_use]
+    pub fn waiters(&self, rid: usize) -> Option<&VecDeque<RCBResource>> {
+        self.resource(rid).map(|rcb| &rcb.waitlist)
+    }
+
+    /// Ready PIDs of one priority level from front to back
+    #[must_use]
+    pub fn ready_queue(&self, priority: usize) -> Option<impl Iterator<Item = usize> + '_> {
+        (priority < self.ready_list.len()).then(|| self.ready_list.level(priority))
+    }
+
+    /// Every deadlock recovery action since the last reset, oldest first
+    #[must_use]
+    pub fn recovery_log(&self) -> &[RecoveryAction] {
+        &self.recovery_log
+    }
+
+    /// Periodic jobs that didn't finish by their deadline since the last reset, oldest first
+    #[must_use]
+    pub fn deadline_misses(&self) -> &[DeadlineMiss] {
+        &self.deadline_misses
+    }
+
+    /// `None` unless the process is blocked
+    #[must_use]
+    pub fn blocked_on(&self, pid: usize) -> Option<BlockedOn> {
+        let pcb = self.process(pid)?;
+        if !matches!(pcb.state, PCBState::BLOCKED | PCBState::SUSPENDED_BLOCKED) {
+            return None;
+        }
+
+        if let Some(child) = pcb.waiting_on {
+            return Some(BlockedOn::Child(child));
+        }
+
+        if pcb.mailbox.receiving {
+            return Some(BlockedOn::Receive);
+        }
+
+        let resource = self.rcb_list.iter().enumerate().find_map(|(rid, rcb)| {
+            rcb.waitlist
+                .iter()
+                .find(|x| x.pid == pid)
+                .map(|x| BlockedOn::Resource {
+                    rid,
+                    units: x.units,
+                })
+        });
+        let mutex = || {
+            self.mutex_list
+                .iter()
+                .position(|mutex| mutex.waitlist.contains(&pid))
+                .map(BlockedOn::Mutex)
+        };
+        let semaphore = || {
+            self.semaphore_list
+                .iter()
+                .position(|semaphore| semaphore.waitlist.contains(&pid))
+                .map(BlockedOn::Semaphore)
+        };
+        let device = || {
+            self.device_list
+                .iter()
+                .position(|device| device.queue.iter().any(|x| x.pid == pid))
+                .map(BlockedOn::Device)
+        };
+        let send = || {
+            self.pcb_list
+                .iter()
+                .position(|receiver| {
+                    receiver.as_ref().is_some_and(|receiver| {
+                        receiver.mailbox.waitlist.iter().any(|x| x.sender == pid)
+                    })
+                })
+                .map(BlockedOn::Send)
+        };
+
+        resource
+            .or_else(mutex)
+            .or_else(semaphore)
+            .or_else(device)
+            .or_else(send)
+    }
+}
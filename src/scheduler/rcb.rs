@@ -1,23 +1,177 @@
-#[derive(Debug, PartialEq, Eq)]
+use std::collections::VecDeque;
+
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RCBResource {
     pub pid: usize,
     pub units: usize,
+    /// Clock tick at which the request gives up, never when `None`
+    pub expires: Option<usize>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RCB {
+    pub name: Option<String>,
     pub inventory: usize,
     pub units_available: usize,
-    pub waitlist: Vec<RCBResource>,
+    pub waitlist: VecDeque<RCBResource>,
 }
 
 impl RCB {
     #[must_use]
-    pub fn new(inventory: usize) -> Self {
+    pub const fn new(inventory: usize) -> Self {
         Self {
+            name: None,
             inventory,
             units_available: inventory,
-            waitlist: Vec::new(),
+            waitlist: VecDeque::new(),
+        }
+    }
+}
+
+impl Scheduler {
+    /// Adds a resource after the existing ones, returning the running PID
+    pub fn make_resource(&mut self, inventory: i32, name: Option<&str>) -> Option<usize> {
+        let inventory = usize::try_from(inventory).ok()?;
+
+        if let Some(name) = name {
+            // Numeric Names Would Be Mistaken For RIDs
+            if name.is_empty() || name.parse::<i64>().is_ok() {
+                self.refuse(Refusal::InvalidArgument, "MKRES: Invalid Resource Name");
+                return None;
+            }
+
+            if self.rid_of(name).is_some() {
+                self.refuse(Refusal::InvalidState, "MKRES: Resource Name Already In Use");
+                return None;
+            }
+        }
+
+        self.rcb_list.push(RCB {
+            name: name.map(String::from),
+            ..RCB::new(inventory)
+        });
+
+        Some(self.running_pid)
+    }
+
+    pub fn add_units(&mut self, rid: i32, units: i32) -> Option<usize> {
+        let rid = usize::try_from(rid).ok()?;
+        let units = usize::try_from(units).ok()?;
+
+        // Bounds Check
+        if rid >= self.rcb_list.len() {
+            self.refuse(Refusal::UnknownId, "ADDUNITS: RID Out Of Bounds");
+            return None;
+        }
+
+        if units == 0 {
+            self.refuse(Refusal::InvalidArgument, "ADDUNITS: Units Cannot Be 0");
+            return None;
         }
+
+        let rcb = &mut self.rcb_list[rid];
+        rcb.inventory += units;
+        rcb.units_available += units;
+
+        self.grant_waiters(rid);
+
+        Some(self.scheduler())
+    }
+
+    /// Shrinks the inventory. Units that are held are only taken if `preempt` is set,
+    /// from the holders with the lowest priority first. Waiting requests that no longer fit
+    /// in the inventory fail.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a holder of the resource has no PCB
+    pub fn remove_units(&mut self, rid: i32, units: i32, preempt: bool) -> Option<usize> {
+        let rid = usize::try_from(rid).ok()?;
+        let units = usize::try_from(units).ok()?;
+
+        // Bounds Check
+        if rid >= self.rcb_list.len() {
+            self.refuse(Refusal::UnknownId, "RMUNITS: RID Out Of Bounds");
+            return None;
+        }
+
+        if units == 0 {
+            self.refuse(Refusal::InvalidArgument, "RMUNITS: Units Cannot Be 0");
+            return None;
+        }
+
+        if units > self.rcb_list[rid].inventory {
+            self.refuse(Refusal::InvalidArgument, "RMUNITS: Units Exceeds Inventory");
+            return None;
+        }
+
+        let mut shortfall = units.saturating_sub(self.rcb_list[rid].units_available);
+        if shortfall > 0 && !preempt {
+            self.refuse(Refusal::InvalidState, "RMUNITS: Units Are Held");
+            return None;
+        }
+
+        // Preempt The Least Important Holders, Youngest First On Ties
+        let mut holders: Vec<(usize, usize)> = self.holders(rid).collect();
+        holders.sort_by_key(|&(pid, _)| {
+            let pcb = self.pcb_list[pid]
+                .as_ref()
+                .expect("RMUNITS: PCB should exist.");
+            (pcb.priority, std::cmp::Reverse(pid))
+        });
+
+        for (pid, held) in holders {
+            if shortfall == 0 {
+                break;
+            }
+
+            let taken = held.min(shortfall);
+            let resources = &mut self.pcb_list[pid]
+                .as_mut()
+                .expect("RMUNITS: PCB should exist.")
+                .resources;
+            let position = resources
+                .iter()
+                .position(|x| x.rid == rid)
+                .expect("RMUNITS: Holder should hold the resource.");
+            if resources[position].units > taken {
+                resources[position].units -= taken;
+            } else {
+                resources.remove(position);
+            }
+
+            self.rcb_list[rid].units_available += taken;
+            shortfall -= taken;
+        }
+
+        let rcb = &mut self.rcb_list[rid];
+        rcb.units_available -= units;
+        rcb.inventory -= units;
+
+        // Requests That No Longer Fit Would Wait Forever
+        let inventory = rcb.inventory;
+        let unsatisfiable: Vec<usize> = rcb
+            .waitlist
+            .iter()
+            .filter(|request| {
+                let held = self.pcb_list[request.pid]
+                    .iter()
+                    .flat_map(|pcb| &pcb.resources)
+                    .filter(|x| x.rid == rid)
+                    .map(|x| x.units)
+                    .sum::<usize>();
+                request.units + held > inventory
+            })
+            .map(|request| request.pid)
+            .collect();
+
+        for pid in unsatisfiable {
+            self.fail_request(pid, rid);
+        }
+
+        Some(self.scheduler())
     }
 }
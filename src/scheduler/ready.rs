@@ -0,0 +1,153 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Link {
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Level {
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+/// FIFO queue per priority level, linked through one slot per PID so pushing, removing and
+/// rotating a process are O(1). A bitmap of non-empty levels finds the highest one in O(1).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ReadyList {
+    levels: Vec<Level>,
+    links: Vec<Link>,
+    /// Level each PID is queued in
+    queued: Vec<Option<usize>>,
+    bitmap: u64,
+}
+
+impl ReadyList {
+    /// # Panics
+    ///
+    /// Will panic if there are more levels than bits in the bitmap
+    #[must_use]
+    pub fn new(levels: usize, processes: usize) -> Self {
+        assert!(
+            levels <= 64,
+            "READY: At most 64 priority levels are supported."
+        );
+
+        Self {
+            levels: vec![Level::default(); levels],
+            links: vec![Link::default(); processes],
+            queued: vec![None; processes],
+            bitmap: 0,
+        }
+    }
+
+    /// Number of priority levels
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bitmap == 0
+    }
+
+    #[must_use]
+    pub fn contains(&self, pid: usize) -> bool {
+        self.queued.get(pid).is_some_and(Option::is_some)
+    }
+
+    /// Highest non-empty priority level
+    #[must_use]
+    pub const fn highest(&self) -> Option<usize> {
+        match self.bitmap {
+            0 => None,
+            bitmap => Some(63 - bitmap.leading_zeros() as usize),
+        }
+    }
+
+    #[must_use]
+    pub fn front(&self, level: usize) -> Option<usize> {
+        self.levels[level].head
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the PID is already queued
+    pub fn push_back(&mut self, level: usize, pid: usize) {
+        assert!(
+            !self.contains(pid),
+            "READY: PID should not be queued twice."
+        );
+
+        let tail = self.levels[level].tail;
+        self.links[pid] = Link {
+            prev: tail,
+            next: None,
+        };
+        match tail {
+            Some(tail) => self.links[tail].next = Some(pid),
+            None => self.levels[level].head = Some(pid),
+        }
+
+        self.levels[level].tail = Some(pid);
+        self.queued[pid] = Some(level);
+        self.bitmap |= 1 << level;
+    }
+
+    /// Returns whether the PID was queued
+    pub fn remove(&mut self, pid: usize) -> bool {
+        let Some(level) = self.queued.get(pid).copied().flatten() else {
+            return false;
+        };
+
+        let Link { prev, next } = self.links[pid];
+        match prev {
+            Some(prev) => self.links[prev].next = next,
+            None => self.levels[level].head = next,
+        }
+        match next {
+            Some(next) => self.links[next].prev = prev,
+            None => self.levels[level].tail = prev,
+        }
+
+        self.links[pid] = Link::default();
+        self.queued[pid] = None;
+        if self.levels[level].head.is_none() {
+            self.bitmap &= !(1 << level);
+        }
+
+        true
+    }
+
+    /// PIDs of one level from front to back
+    pub fn level(&self, level: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.levels[level].head, |&pid| self.links[pid].next)
+    }
+
+    /// Every level from lowest to highest priority
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = usize> + '_> {
+        (0..self.levels.len()).map(|level| self.level(level))
+    }
+}
+
+impl fmt::Debug for ReadyList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(Iterator::collect::<Vec<_>>))
+            .finish()
+    }
+}
+
+/// Compares level by level, e.g. `ready_list == [vec![0], vec![1], Vec::new()]`
+impl<const N: usize> PartialEq<[Vec<usize>; N]> for ReadyList {
+    fn eq(&self, other: &[Vec<usize>; N]) -> bool {
+        self.len() == N
+            && self
+                .iter()
+                .zip(other)
+                .all(|(level, expected)| level.eq(expected.iter().copied()))
+    }
+}
@@ -0,0 +1,210 @@
+use crate::scheduler::config::SchedulingPolicy;
+use crate::scheduler::pcb::{CreateOptions, PCBState};
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodicTask {
+    pub period: usize,
+    pub execution: usize,
+    /// Relative to each job's release
+    pub deadline: usize,
+    /// Release time of the current job
+    pub release: usize,
+    /// Execution ticks the current job still needs
+    pub remaining: usize,
+    pub missed: bool,
+}
+
+impl PeriodicTask {
+    #[must_use]
+    pub const fn absolute_deadline(&self) -> usize {
+        self.release + self.deadline
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadlineMiss {
+    pub pid: usize,
+    pub deadline: usize,
+}
+
+impl Scheduler {
+    /// # Panics
+    ///
+    /// Will panic if the running process has no PCB
+    pub fn create_periodic(
+        &mut self,
+        period: i32,
+        execution: i32,
+        deadline: Option<i32>,
+    ) -> Option<usize> {
+        let period = usize::try_from(period).ok()?;
+        let execution = usize::try_from(execution).ok()?;
+        let deadline = deadline.map_or(Some(period), |deadline| usize::try_from(deadline).ok())?;
+
+        if self.config.policy != SchedulingPolicy::EarliestDeadlineFirst
+            && self.config.policy != SchedulingPolicy::RateMonotonic
+        {
+            self.refuse(
+                Refusal::InvalidState,
+                "RT: Periodic Tasks Need An EDF Or Rate-Monotonic Policy",
+            );
+            return None;
+        }
+
+        if execution == 0 || execution > deadline.min(period) {
+            self.refuse(
+                Refusal::InvalidArgument,
+                "RT: Execution Time Must Fit Within The Deadline And Period",
+            );
+            return None;
+        }
+
+        let task = PeriodicTask {
+            period,
+            execution,
+            deadline,
+            release: self.clock,
+            remaining: execution,
+            missed: false,
+        };
+
+        // Admission Control
+        if !self.is_schedulable(&task) {
+            self.refuse(
+                Refusal::InvalidState,
+                "RT: Task Set Would Not Be Schedulable",
+            );
+            return None;
+        }
+
+        let Some(empty_pid) = self.free_pids.pop_first() else {
+            self.refuse(Refusal::InvalidState, "RT: No Empty PCBs");
+            return None;
+        };
+
+        // Periodic Tasks Live In The Top Level But Are Ordered By The Policy
+        let priority = self.ready_list.len() - 1;
+        self.spawn(empty_pid, priority, &CreateOptions::default(), Some(task));
+
+        Some(self.scheduler())
+    }
+
+    /// Rate-monotonic uses the Liu and Layland bound `U <= n(2^(1/n) - 1)`.
+    /// EDF is exact for implicit deadlines, `sum(C / min(D, T)) <= 1`.
+    #[allow(clippy::cast_precision_loss)]
+    fn is_schedulable(&self, new_task: &PeriodicTask) -> bool {
+        let tasks: Vec<&PeriodicTask> = self
+            .pcb_list
+            .iter()
+            .flatten()
+            .filter_map(|pcb| pcb.task.as_ref())
+            .chain(std::iter::once(new_task))
+            .collect();
+        let n = tasks.len() as f64;
+
+        match self.config.policy {
+            SchedulingPolicy::RateMonotonic => {
+                let utilization: f64 = tasks
+                    .iter()
+                    .map(|task| task.execution as f64 / task.period as f64)
+                    .sum();
+
+                utilization <= n * ((1.0 / n).exp2() - 1.0)
+            }
+            SchedulingPolicy::EarliestDeadlineFirst => {
+                let density: f64 = tasks
+                    .iter()
+                    .map(|task| task.execution as f64 / task.deadline.min(task.period) as f64)
+                    .sum();
+
+                density <= 1.0
+            }
+            SchedulingPolicy::Priority
+            | SchedulingPolicy::CompletelyFair
+            | SchedulingPolicy::ShortestJobFirst
+            | SchedulingPolicy::ShortestRemainingTimeFirst => false,
+        }
+    }
+
+    /// Charges the running jobs for the tick that just ended
+    pub(super) fn run_periodic_tasks(&mut self) {
+        for core in 0..self.cpu_list.len() {
+            let pid = self.cpu_list[core].running_pid;
+            let Some(task) = self.pcb_list[pid]
+                .as_mut()
+                .and_then(|pcb| pcb.task.as_mut())
+            else {
+                continue;
+            };
+
+            task.remaining = task.remaining.saturating_sub(1);
+
+            // Job Done, Sleep Until The Next Release
+            if task.remaining == 0 {
+                self.block(pid);
+            }
+        }
+    }
+
+    /// Records deadline misses and releases new jobs at the current clock
+    pub(super) fn release_periodic_tasks(&mut self) {
+        // Periodic Tasks Only Exist Under The Real-Time Policies
+        if self.config.policy != SchedulingPolicy::EarliestDeadlineFirst
+            && self.config.policy != SchedulingPolicy::RateMonotonic
+        {
+            return;
+        }
+
+        for pid in 0..self.pcb_list.len() {
+            let clock = self.clock;
+            let Some(pcb) = self.pcb_list[pid].as_mut() else {
+                continue;
+            };
+
+            if pcb.state == PCBState::ZOMBIE {
+                continue;
+            }
+
+            let Some(task) = pcb.task.as_mut() else {
+                continue;
+            };
+
+            if task.remaining > 0 && !task.missed && clock >= task.absolute_deadline() {
+                task.missed = true;
+                self.deadline_misses.push(DeadlineMiss {
+                    pid,
+                    deadline: task.absolute_deadline(),
+                });
+            }
+
+            if clock < task.release + task.period {
+                continue;
+            }
+
+            // The Unfinished Work Of A Late Job Is Dropped
+            let sleeping = task.remaining == 0;
+            task.release += task.period;
+            task.remaining = task.execution;
+            task.missed = false;
+
+            if sleeping {
+                self.unblock(pid);
+            }
+        }
+    }
+
+    pub(super) fn deadline_order(&self, pid: usize) -> Option<usize> {
+        let task = self.pcb_list[pid].as_ref()?.task.as_ref()?;
+
+        match self.config.policy {
+            SchedulingPolicy::EarliestDeadlineFirst => Some(task.absolute_deadline()),
+            SchedulingPolicy::RateMonotonic => Some(task.period),
+            SchedulingPolicy::Priority
+            | SchedulingPolicy::CompletelyFair
+            | SchedulingPolicy::ShortestJobFirst
+            | SchedulingPolicy::ShortestRemainingTimeFirst => None,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::scheduler::Scheduler;
+
+/// Why the scheduler refused a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refusal {
+    /// An ID is out of bounds or names nothing live
+    UnknownId,
+    /// The running process may not act on the target, or process 0 may not do it at all
+    NotPermitted,
+    /// An argument is out of range or malformed
+    InvalidArgument,
+    /// The command doesn't fit the current state, like resuming a ready process
+    InvalidState,
+}
+
+impl Scheduler {
+    /// Why the last command run through `apply` was refused, `None` if it wasn't or no
+    /// reason was given
+    #[must_use]
+    pub const fn last_refusal(&self) -> Option<Refusal> {
+        self.refusal
+    }
+
+    /// Reports a refused command, the caller then returns `None`
+    pub(super) fn refuse(&mut self, refusal: Refusal, message: &str) {
+        eprintln!("{message}");
+        self.refusal = Some(refusal);
+    }
+}
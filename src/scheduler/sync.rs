@@ -0,0 +1,186 @@
+use crate::scheduler::refusal::Refusal;
+use crate::scheduler::Scheduler;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mutex {
+    pub owner: Option<usize>,
+    pub waitlist: Vec<usize>,
+}
+
+impl Mutex {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            owner: None,
+            waitlist: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Semaphore {
+    pub value: usize,
+    pub waitlist: Vec<usize>,
+}
+
+impl Semaphore {
+    #[must_use]
+    pub const fn new(value: usize) -> Self {
+        Self {
+            value,
+            waitlist: Vec::new(),
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn lock(&mut self, mid: i32) -> Option<usize> {
+        let mid = usize::try_from(mid).ok()?;
+
+        // Bounds Check
+        let Some(mutex) = self.mutex_list.get_mut(mid) else {
+            self.refuse(Refusal::UnknownId, "LOCK: MID Out Of Bounds");
+            return None;
+        };
+
+        match mutex.owner {
+            None => mutex.owner = Some(self.running_pid),
+            Some(owner) if owner == self.running_pid => {
+                self.refuse(
+                    Refusal::InvalidState,
+                    "LOCK: Process Already Owns The Mutex",
+                );
+                return None;
+            }
+            Some(_) => {
+                // Process 0 Can't Block
+                if self.running_pid == 0 {
+                    self.refuse(
+                        Refusal::NotPermitted,
+                        "LOCK: Process 0 Can't Block On A Mutex",
+                    );
+                    return None;
+                }
+
+                mutex.waitlist.push(self.running_pid);
+                self.block_running();
+            }
+        }
+
+        Some(self.scheduler())
+    }
+
+    pub fn unlock(&mut self, mid: i32) -> Option<usize> {
+        let mid = usize::try_from(mid).ok()?;
+
+        // Bounds Check
+        let Some(mutex) = self.mutex_list.get(mid) else {
+            self.refuse(Refusal::UnknownId, "UNLOCK: MID Out Of Bounds");
+            return None;
+        };
+
+        // Only The Owner Can Unlock
+        if mutex.owner != Some(self.running_pid) {
+            self.refuse(
+                Refusal::NotPermitted,
+                "UNLOCK: Process Does Not Own The Mutex",
+            );
+            return None;
+        }
+
+        self.hand_off_mutex(mid);
+
+        Some(self.scheduler())
+    }
+
+    pub fn set_semaphore(&mut self, sid: i32, value: i32) -> Option<usize> {
+        let sid = usize::try_from(sid).ok()?;
+        let value = usize::try_from(value).ok()?;
+
+        // Bounds Check
+        let Some(semaphore) = self.semaphore_list.get_mut(sid) else {
+            self.refuse(Refusal::UnknownId, "SEMAPHORE: SID Out Of Bounds");
+            return None;
+        };
+
+        if !semaphore.waitlist.is_empty() {
+            self.refuse(
+                Refusal::InvalidState,
+                "SEMAPHORE: Cannot Set A Semaphore With Waiting Processes",
+            );
+            return None;
+        }
+
+        semaphore.value = value;
+
+        Some(self.running_pid)
+    }
+
+    pub fn semaphore_wait(&mut self, sid: i32) -> Option<usize> {
+        let sid = usize::try_from(sid).ok()?;
+
+        // Bounds Check
+        let Some(semaphore) = self.semaphore_list.get_mut(sid) else {
+            self.refuse(Refusal::UnknownId, "P: SID Out Of Bounds");
+            return None;
+        };
+
+        if semaphore.value > 0 {
+            semaphore.value -= 1;
+        } else {
+            // Process 0 Can't Block
+            if self.running_pid == 0 {
+                self.refuse(
+                    Refusal::NotPermitted,
+                    "P: Process 0 Can't Block On A Semaphore",
+                );
+                return None;
+            }
+
+            semaphore.waitlist.push(self.running_pid);
+            self.block_running();
+        }
+
+        Some(self.scheduler())
+    }
+
+    pub fn semaphore_signal(&mut self, sid: i32) -> Option<usize> {
+        let sid = usize::try_from(sid).ok()?;
+
+        // Bounds Check
+        let Some(semaphore) = self.semaphore_list.get_mut(sid) else {
+            self.refuse(Refusal::UnknownId, "V: SID Out Of Bounds");
+            return None;
+        };
+
+        // Wake The First Waiter Instead Of Incrementing
+        if semaphore.waitlist.is_empty() {
+            semaphore.value += 1;
+        } else {
+            let pid = semaphore.waitlist.remove(0);
+            self.unblock(pid);
+        }
+
+        Some(self.scheduler())
+    }
+
+    fn hand_off_mutex(&mut self, mid: usize) {
+        let mutex = &mut self.mutex_list[mid];
+
+        if mutex.waitlist.is_empty() {
+            mutex.owner = None;
+        } else {
+            let pid = mutex.waitlist.remove(0);
+            mutex.owner = Some(pid);
+            self.unblock(pid);
+        }
+    }
+
+    pub(super) fn release_mutexes(&mut self, pid: usize) {
+        for mid in 0..self.mutex_list.len() {
+            if self.mutex_list[mid].owner == Some(pid) {
+                self.hand_off_mutex(mid);
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::scheduler::Scheduler;
+
+impl Scheduler {
+    /// Fails every blocked request whose timeout has run out at the current clock
+    pub(super) fn expire_requests(&mut self) {
+        while let Some(&(expires, pid)) = self.timers.first() {
+            if expires > self.clock {
+                return;
+            }
+
+            let rid = self
+                .rcb_list
+                .iter()
+                .position(|rcb| rcb.waitlist.iter().any(|x| x.pid == pid))
+                .expect("TIMER: Timed request should be in a waitlist.");
+            self.fail_request(pid, rid);
+        }
+    }
+
+    /// Takes the process's request off the waitlist and makes it ready again with the RID in
+    /// its `failed_request`
+    pub(super) fn fail_request(&mut self, pid: usize, rid: usize) {
+        let waitlist = &mut self.rcb_list[rid].waitlist;
+        if let Some(position) = waitlist.iter().position(|x| x.pid == pid) {
+            if let Some(expires) = waitlist.remove(position).and_then(|x| x.expires) {
+                self.timers.remove(&(expires, pid));
+            }
+        }
+
+        self.pcb_list[pid]
+            .as_mut()
+            .expect("TIMER: PCB should exist.")
+            .failed_request = Some(rid);
+        self.unblock(pid);
+    }
+}
@@ -1,4 +1,6 @@
-use scheduler::process::interactive_shell;
+use scheduler::output::OutputFormat;
+use scheduler::process::{interactive_shell, interactive_shell_with, ShellOptions};
+use scheduler::scheduler::config::{Config, RecoveryStrategy, ResourceConfig, SchedulingPolicy};
 
 #[test]
 fn test_interactive_shell() {
@@ -29,3 +31,333 @@ fn provided_values() {
 
     assert_eq!(output, expected_output);
 }
+
+#[test]
+fn script_extensions() {
+    interactive_shell("files/script-input.txt", "files/script-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/script-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/script-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn expect_assertions() {
+    interactive_shell("files/expect-input.txt", "files/expect-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/expect-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/expect-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn exit_codes() {
+    interactive_shell("files/exit-input.txt", "files/exit-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/exit-temp-output.txt").unwrap();
+    assert_eq!(output, "1 2 1 1 1 1 1 0 2 1 1 ");
+}
+
+#[test]
+fn failed_expect_assertion() {
+    let result = interactive_shell(
+        "files/expect-failure-input.txt",
+        "files/expect-failure-temp-output.txt",
+    );
+    assert_eq!(result, Err("One or more expect assertions failed"));
+
+    // The output is still written
+    let output = std::fs::read_to_string("files/expect-failure-temp-output.txt").unwrap();
+    assert_eq!(output, "0 1 -1 1 ");
+}
+
+#[test]
+fn json_format() {
+    interactive_shell_with(
+        "files/format-input.txt",
+        "files/format-json-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Json,
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/format-json-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/format-json-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn json_error_kinds() {
+    interactive_shell_with(
+        "files/refusal-input.txt",
+        "files/refusal-json-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Json,
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/refusal-json-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/refusal-json-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn verbose_format() {
+    interactive_shell_with(
+        "files/format-input.txt",
+        "files/format-verbose-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Verbose,
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/format-verbose-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/format-verbose-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn partial_output_on_invalid_batch() {
+    let result = interactive_shell("files/partial-input.txt", "files/partial-temp-output.txt");
+    assert_eq!(result, Err("Error reading input file"));
+
+    // Batches before the unclosed repeat are kept
+    let output = std::fs::read_to_string("files/partial-temp-output.txt").unwrap();
+    assert_eq!(output, "0 1 1 ");
+}
+
+#[test]
+fn malformed_batch_keeps_json_well_formed() {
+    interactive_shell_with(
+        "files/malformed-input.txt",
+        "files/malformed-json-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Json,
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    // The malformed commands fail on their own and the array is still closed
+    let output = std::fs::read_to_string("files/malformed-json-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/malformed-json-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn parallel_batches_keep_order() {
+    interactive_shell_with(
+        "files/provided-input.txt",
+        "files/provided-parallel-temp-output.txt",
+        &ShellOptions {
+            jobs: 4,
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/provided-parallel-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/provided-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn printed_output_in_batch_order() {
+    let options = ShellOptions {
+        format: OutputFormat::Json,
+        jobs: 2,
+        ..ShellOptions::default()
+    };
+    interactive_shell_with(
+        "files/messages-input.txt",
+        "files/messages-json-temp-output.txt",
+        &options,
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/messages-json-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/messages-json-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+
+    interactive_shell_with(
+        "files/messages-input.txt",
+        "files/messages-verbose-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Verbose,
+            ..options
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/messages-verbose-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/messages-verbose-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn named_processes_and_resources() {
+    let options = ShellOptions {
+        config: Config {
+            resources: ResourceConfig::parse_list("disk=2, printer=1").unwrap(),
+            ..Config::default()
+        },
+        ..ShellOptions::default()
+    };
+    interactive_shell_with(
+        "files/names-input.txt",
+        "files/names-temp-output.txt",
+        &options,
+    )
+    .unwrap();
+
+    // Plain output still reports PIDs
+    let output = std::fs::read_to_string("files/names-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 2 1 1 1 1 1 1 -1 -1 1 ");
+
+    interactive_shell_with(
+        "files/names-input.txt",
+        "files/names-verbose-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Verbose,
+            ..options
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/names-verbose-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/names-verbose-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn transactions() {
+    interactive_shell(
+        "files/transaction-input.txt",
+        "files/transaction-temp-output.txt",
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/transaction-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 2 2 1 1 -1 1 1 1 1 1 1 1 -1 ");
+}
+
+#[test]
+fn request_timeouts() {
+    interactive_shell("files/timeout-input.txt", "files/timeout-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/timeout-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 1 2 -1 1 1 1 2 2 2 ");
+}
+
+#[test]
+fn deadlock_recovery() {
+    interactive_shell("files/deadlock-input.txt", "files/deadlock-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/deadlock-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 1 2 2 1 0 0 1 1 1 -1 ");
+}
+
+#[test]
+fn automatic_recovery_is_reported() {
+    interactive_shell_with(
+        "files/auto-recovery-input.txt",
+        "files/auto-recovery-verbose-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Verbose,
+            config: Config {
+                deadlock_recovery: RecoveryStrategy::parse("terminate:youngest"),
+                ..Config::default()
+            },
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/auto-recovery-verbose-temp-output.txt").unwrap();
+    let expected_output =
+        std::fs::read_to_string("files/auto-recovery-verbose-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn dynamic_resources() {
+    interactive_shell(
+        "files/inventory-input.txt",
+        "files/inventory-temp-output.txt",
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/inventory-temp-output.txt").unwrap();
+    assert_eq!(output, "0 1 1 -1 1 1 1 1 1 ");
+}
+
+#[test]
+fn process_groups() {
+    interactive_shell("files/group-input.txt", "files/group-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/group-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 1 1 1 1 2 -1 3 1 1 1 1 3 1 1 1 ");
+}
+
+#[test]
+fn deadline_misses_are_reported() {
+    interactive_shell_with(
+        "files/deadline-input.txt",
+        "files/deadline-verbose-temp-output.txt",
+        &ShellOptions {
+            format: OutputFormat::Verbose,
+            config: Config {
+                policy: SchedulingPolicy::parse("edf").unwrap(),
+                ..Config::default()
+            },
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/deadline-verbose-temp-output.txt").unwrap();
+    let expected_output = std::fs::read_to_string("files/deadline-verbose-output.txt").unwrap();
+
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn periodic_tasks_need_a_real_time_policy() {
+    interactive_shell("files/periodic-input.txt", "files/periodic-temp-output.txt").unwrap();
+
+    let output = std::fs::read_to_string("files/periodic-temp-output.txt").unwrap();
+    assert_eq!(output, "-1 0 0 0 ");
+
+    interactive_shell_with(
+        "files/periodic-input.txt",
+        "files/periodic-edf-temp-output.txt",
+        &ShellOptions {
+            config: Config {
+                policy: SchedulingPolicy::parse("edf").unwrap(),
+                ..Config::default()
+            },
+            ..ShellOptions::default()
+        },
+    )
+    .unwrap();
+
+    let output = std::fs::read_to_string("files/periodic-edf-temp-output.txt").unwrap();
+    assert_eq!(output, "1 1 0 0 ");
+}
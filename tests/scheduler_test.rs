@@ -1,7 +1,22 @@
+use std::collections::{BTreeSet, VecDeque};
+
 use scheduler::scheduler::{
+    burst::Burst,
+    command::{Command, DryRun},
+    config::{
+        BalanceStrategy, Config, ExitPolicy, QueueMode, RecoveryStrategy, ResourceConfig,
+        SchedulingPolicy, VictimPolicy,
+    },
+    deadlock::RecoveryAction,
     defaults::{pcb_list_default, rcb_list_default},
-    pcb::{PCBResource, PCBState, PCB},
+    device::IORequest,
+    mailbox::{Mailbox, Message},
+    pcb::{CreateOptions, ExitStatus, PCBResource, PCBState, PCB},
+    query::BlockedOn,
     rcb::{RCBResource, RCB},
+    realtime::DeadlineMiss,
+    refusal::Refusal,
+    sync::{Mutex, Semaphore},
     Scheduler,
 };
 
@@ -42,10 +57,22 @@ fn test_scheduler_create() {
     assert_eq!(
         scheduler.pcb_list[1].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(0),
+            group: 0,
             priority: 1,
             state: PCBState::READY,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: Vec::new()
         }
     );
@@ -82,27 +109,41 @@ fn general_request() {
     assert_eq!(
         scheduler.rcb_list[1],
         RCB {
+            name: None,
             inventory: 1,
             units_available: 1,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
     assert_eq!(scheduler.request(1, 1), Some(1));
     assert_eq!(
         scheduler.rcb_list[1],
         RCB {
+            name: None,
             inventory: 1,
             units_available: 0,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
     assert_eq!(
         scheduler.pcb_list[1].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(0),
+            group: 0,
             priority: 1,
             state: PCBState::READY,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: vec![PCBResource { rid: 1, units: 1 }]
         }
     );
@@ -113,18 +154,31 @@ fn general_request() {
     assert_eq!(
         scheduler.rcb_list[1],
         RCB {
+            name: None,
             inventory: 1,
             units_available: 0,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
     assert_eq!(
         scheduler.pcb_list[1].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(0),
+            group: 0,
             priority: 1,
             state: PCBState::READY,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: vec![
                 PCBResource { rid: 1, units: 1 },
                 PCBResource { rid: 3, units: 2 }
@@ -143,9 +197,10 @@ fn general_release() {
     assert_eq!(
         scheduler.rcb_list[1],
         RCB {
+            name: None,
             inventory: 1,
             units_available: 0,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
 
@@ -154,9 +209,10 @@ fn general_release() {
     assert_eq!(
         scheduler.rcb_list[1],
         RCB {
+            name: None,
             inventory: 1,
             units_available: 1,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
 }
@@ -337,8 +393,16 @@ fn multiple_releases_with_skipping() {
     assert_eq!(
         scheduler.rcb_list[3].waitlist,
         vec![
-            RCBResource { pid: 2, units: 3 },
-            RCBResource { pid: 3, units: 3 }
+            RCBResource {
+                pid: 2,
+                units: 3,
+                expires: None
+            },
+            RCBResource {
+                pid: 3,
+                units: 3,
+                expires: None
+            }
         ]
     );
 }
@@ -391,19 +455,36 @@ fn destroy_with_resources() {
     assert_eq!(
         scheduler.pcb_list[3].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(1),
+            group: 0,
             priority: 2,
             state: PCBState::BLOCKED,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: Vec::new()
         }
     );
     assert_eq!(
         scheduler.rcb_list[3],
         RCB {
+            name: None,
             inventory: 3,
             units_available: 0,
-            waitlist: vec![RCBResource { pid: 3, units: 3 }]
+            waitlist: VecDeque::from([RCBResource {
+                pid: 3,
+                units: 3,
+                expires: None
+            }])
         }
     );
 
@@ -412,9 +493,10 @@ fn destroy_with_resources() {
     assert_eq!(
         scheduler.rcb_list[3],
         RCB {
+            name: None,
             inventory: 3,
             units_available: 0,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
 
@@ -423,13 +505,34 @@ fn destroy_with_resources() {
     assert_eq!(
         scheduler.rcb_list[3],
         RCB {
+            name: None,
             inventory: 3,
             units_available: 3,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
 }
 
+#[test]
+fn destroy_blocked_process_holding_part_of_its_request() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(2); // Process 1
+    scheduler.request(2, 1);
+    scheduler.create(2); // Process 2
+    scheduler.timeout();
+
+    // Process 2 holds one unit of resource 2 and waits for the other
+    assert_eq!(scheduler.request(2, 1), Some(2));
+    assert_eq!(scheduler.request(2, 1), Some(1));
+
+    // The unit it releases must not be granted back to it
+    assert_eq!(scheduler.destroy(2), Some(1));
+    assert_eq!(scheduler.ready_list, [vec![0], Vec::new(), vec![1]]);
+    assert_eq!(scheduler.rcb_list[2].units_available, 1);
+    assert!(scheduler.rcb_list[2].waitlist.is_empty());
+}
+
 #[test]
 fn add_requests() {
     let mut scheduler = Scheduler::new();
@@ -472,19 +575,36 @@ fn multiple_requests() {
     assert_eq!(
         scheduler.pcb_list[2].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(1),
+            group: 0,
             priority: 2,
             state: PCBState::BLOCKED,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: vec![PCBResource { rid: 3, units: 2 }]
         }
     );
     assert_eq!(
         scheduler.rcb_list[3],
         RCB {
+            name: None,
             inventory: 3,
             units_available: 0,
-            waitlist: vec![RCBResource { pid: 2, units: 1 }]
+            waitlist: VecDeque::from([RCBResource {
+                pid: 2,
+                units: 1,
+                expires: None
+            }])
         }
     );
 
@@ -492,19 +612,32 @@ fn multiple_requests() {
     assert_eq!(
         scheduler.pcb_list[2].as_ref().unwrap(),
         &PCB {
+            name: None,
             children: Vec::new(),
             parent: Some(1),
+            group: 0,
             priority: 2,
             state: PCBState::READY,
+            exit_code: None,
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
             resources: vec![PCBResource { rid: 3, units: 3 }]
         }
     );
     assert_eq!(
         scheduler.rcb_list[3],
         RCB {
+            name: None,
             inventory: 3,
             units_available: 0,
-            waitlist: Vec::new()
+            waitlist: VecDeque::new()
         }
     );
 }
@@ -531,3 +664,1455 @@ fn lowest_pid_value() {
     scheduler.destroy(1);
     assert_eq!(scheduler.create(2), Some(2));
 }
+
+#[test]
+fn suspend_and_resume_ready() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(2); // Process 1
+    scheduler.create(1); // Process 2
+    scheduler.create(1); // Process 3
+
+    assert_eq!(scheduler.suspend(2), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::SUSPENDED_READY
+    );
+    assert_eq!(scheduler.ready_list, [vec![0], vec![3], vec![1]]);
+    assert_eq!(scheduler.suspend(2), None);
+
+    assert_eq!(scheduler.resume(2), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::READY
+    );
+    assert_eq!(scheduler.ready_list, [vec![0], vec![3, 2], vec![1]]);
+    assert_eq!(scheduler.resume(2), None);
+}
+
+#[test]
+fn suspended_blocked_process_granted_resource() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(2); // Process 1
+    scheduler.create(1); // Process 2
+    scheduler.create(2); // Process 3
+    scheduler.request(1, 1);
+    assert_eq!(scheduler.timeout(), Some(3));
+    assert_eq!(scheduler.request(1, 1), Some(1));
+
+    // Process 1 suspends its blocked child, which keeps its waitlist entry
+    assert_eq!(scheduler.suspend(3), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[3].as_ref().unwrap().state,
+        PCBState::SUSPENDED_BLOCKED
+    );
+    assert_eq!(
+        scheduler.rcb_list[1].waitlist,
+        vec![RCBResource {
+            pid: 3,
+            units: 1,
+            expires: None
+        }]
+    );
+
+    // The grant doesn't make process 3 runnable
+    assert_eq!(scheduler.release(1, 1), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[3].as_ref().unwrap().state,
+        PCBState::SUSPENDED_READY
+    );
+    assert_eq!(
+        scheduler.pcb_list[3].as_ref().unwrap().resources,
+        vec![PCBResource { rid: 1, units: 1 }]
+    );
+    assert_eq!(scheduler.ready_list, [vec![0], vec![2], vec![1]]);
+
+    assert_eq!(scheduler.resume(3), Some(1));
+    assert_eq!(scheduler.ready_list, [vec![0], vec![2], vec![1, 3]]);
+}
+
+#[test]
+fn exit_cascade() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(2); // Process 1
+    scheduler.create(1); // Process 2
+
+    assert_eq!(scheduler.exit(0), Some(0));
+    assert_eq!(scheduler.pcb_list[2], None);
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().children, Vec::new());
+    assert_eq!(scheduler.ready_list, [vec![0], Vec::new(), Vec::new()]);
+    assert_eq!(scheduler.exit(0), None);
+}
+
+#[test]
+fn exit_reparent_to_init() {
+    let mut scheduler = Scheduler::with_config(Config {
+        exit_policy: ExitPolicy::ReparentToInit,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+    scheduler.create(0); // Process 3
+    scheduler.create(0); // Process 4
+
+    assert_eq!(scheduler.running_pid, 2);
+    assert_eq!(scheduler.exit(0), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[0].as_ref().unwrap().children,
+        vec![1, 3, 4]
+    );
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().children, vec![2]);
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().children, Vec::new());
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().parent, Some(0));
+    assert_eq!(scheduler.pcb_list[4].as_ref().unwrap().parent, Some(0));
+    assert_eq!(scheduler.ready_list, [vec![0, 3, 4], vec![1], Vec::new()]);
+}
+
+#[test]
+fn exit_reparent_to_grandparent() {
+    let mut scheduler = Scheduler::with_config(Config {
+        exit_policy: ExitPolicy::ReparentToGrandparent,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+    scheduler.create(0); // Process 3
+
+    assert_eq!(scheduler.exit(0), Some(1));
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().children, vec![2, 3]);
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().parent, Some(1));
+
+    // The re-parented process can now be destroyed by its new parent
+    assert_eq!(scheduler.destroy(3), Some(1));
+    assert_eq!(scheduler.ready_list, [vec![0], vec![1], Vec::new()]);
+}
+
+#[test]
+fn zombie_reaped_by_wait() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+    scheduler.request(1, 1);
+
+    assert_eq!(scheduler.exit(7), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap(),
+        &PCB {
+            name: None,
+            children: Vec::new(),
+            parent: Some(1),
+            group: 0,
+            priority: 2,
+            state: PCBState::ZOMBIE,
+            exit_code: Some(7),
+            collected: None,
+            waiting_on: None,
+            failed_request: None,
+            mailbox: Mailbox::new(),
+            affinity: None,
+            core: None,
+            task: None,
+            vruntime: 0,
+            burst: Burst::default(),
+            resources: Vec::new()
+        }
+    );
+    assert_eq!(scheduler.rcb_list[1].units_available, 1);
+
+    // The zombie's slot isn't reused until it is reaped
+    assert_eq!(scheduler.create(0), Some(1));
+    assert!(scheduler.pcb_list[3].is_some());
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().collected, None);
+    assert_eq!(scheduler.wait(2), Some(1));
+    assert_eq!(scheduler.pcb_list[2], None);
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().children, vec![3]);
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().collected,
+        Some(ExitStatus { pid: 2, code: 7 })
+    );
+    assert_eq!(scheduler.create(0), Some(1));
+    assert!(scheduler.pcb_list[2].is_some());
+}
+
+#[test]
+fn wait_blocks_until_child_exits() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(2); // Process 1
+    scheduler.create(1); // Process 2
+
+    assert_eq!(scheduler.wait(2), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().state,
+        PCBState::BLOCKED
+    );
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().waiting_on, Some(2));
+    assert_eq!(scheduler.ready_list, [vec![0], vec![2], Vec::new()]);
+
+    // Exiting wakes the parent and is reaped straight away
+    assert_eq!(scheduler.exit(3), Some(1));
+    assert_eq!(scheduler.pcb_list[2], None);
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().state,
+        PCBState::READY
+    );
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().waiting_on, None);
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().collected,
+        Some(ExitStatus { pid: 2, code: 3 })
+    );
+    assert_eq!(scheduler.ready_list, [vec![0], Vec::new(), vec![1]]);
+
+    // Only direct children can be waited on
+    assert_eq!(scheduler.wait(0), None);
+    assert_eq!(scheduler.wait(2), None);
+}
+
+#[test]
+fn mutex_lock_and_hand_off() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(1); // Process 1
+    assert_eq!(scheduler.lock(0), Some(1));
+    assert_eq!(scheduler.lock(0), None);
+    scheduler.create(2); // Process 2
+
+    assert_eq!(scheduler.lock(0), Some(1));
+    assert_eq!(
+        scheduler.mutex_list[0],
+        Mutex {
+            owner: Some(1),
+            waitlist: vec![2]
+        }
+    );
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::BLOCKED
+    );
+
+    // Unlocking hands the mutex straight to the first waiter
+    assert_eq!(scheduler.unlock(0), Some(2));
+    assert_eq!(
+        scheduler.mutex_list[0],
+        Mutex {
+            owner: Some(2),
+            waitlist: Vec::new()
+        }
+    );
+    assert_eq!(scheduler.unlock(4), None);
+
+    // Destroying the owner frees the mutex
+    assert_eq!(scheduler.destroy(2), Some(1));
+    assert_eq!(scheduler.mutex_list[0], Mutex::new());
+    assert_eq!(scheduler.unlock(0), None);
+}
+
+#[test]
+fn semaphore_producer_consumer() {
+    let mut scheduler = Scheduler::new();
+
+    assert_eq!(scheduler.set_semaphore(0, 0), Some(0));
+    scheduler.create(1); // Process 1, the producer
+    scheduler.create(2); // Process 2, the consumer
+
+    // The consumer blocks on the empty semaphore
+    assert_eq!(scheduler.semaphore_wait(0), Some(1));
+    assert_eq!(
+        scheduler.semaphore_list[0],
+        Semaphore {
+            value: 0,
+            waitlist: vec![2]
+        }
+    );
+    assert_eq!(scheduler.set_semaphore(0, 1), None);
+
+    // A different process signals and wakes it
+    assert_eq!(scheduler.semaphore_signal(0), Some(2));
+    assert_eq!(scheduler.semaphore_list[0], Semaphore::new(0));
+
+    assert_eq!(scheduler.semaphore_signal(0), Some(2));
+    assert_eq!(scheduler.semaphore_wait(0), Some(2));
+    assert_eq!(scheduler.semaphore_list[0], Semaphore::new(0));
+
+    // Process 0 can't block
+    scheduler.init();
+    assert_eq!(scheduler.semaphore_wait(0), None);
+    assert_eq!(scheduler.semaphore_wait(4), None);
+}
+
+#[test]
+fn receive_blocks_until_send() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+
+    // An empty mailbox blocks the receiver
+    assert_eq!(scheduler.receive(), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::BLOCKED
+    );
+
+    // Sending wakes it and hands over the value
+    assert_eq!(scheduler.send(2, 42), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().mailbox,
+        Mailbox {
+            last_received: Some(Message {
+                sender: 1,
+                value: 42
+            }),
+            ..Mailbox::new()
+        }
+    );
+
+    // Queued messages are received in order without blocking
+    assert_eq!(scheduler.send(2, 1), Some(2));
+    assert_eq!(scheduler.send(2, 2), Some(2));
+    assert_eq!(scheduler.receive(), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[2]
+            .as_ref()
+            .unwrap()
+            .mailbox
+            .last_received,
+        Some(Message {
+            sender: 2,
+            value: 1
+        })
+    );
+
+    assert_eq!(scheduler.send(16, 1), None);
+    assert_eq!(scheduler.send(5, 1), None);
+    scheduler.init();
+    assert_eq!(scheduler.receive(), None);
+}
+
+#[test]
+fn bounded_mailbox_blocks_sender() {
+    let mut scheduler = Scheduler::with_config(Config {
+        mailbox_capacity: Some(1),
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+
+    assert_eq!(scheduler.send(1, 1), Some(2));
+    assert_eq!(scheduler.send(1, 2), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::BLOCKED
+    );
+
+    // Receiving makes room and wakes the sender
+    assert_eq!(scheduler.receive(), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().mailbox.messages,
+        vec![Message {
+            sender: 2,
+            value: 2
+        }]
+    );
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().mailbox.waitlist,
+        Vec::new()
+    );
+}
+
+#[test]
+fn io_completes_after_ticks() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+    scheduler.create(2); // Process 3
+
+    assert_eq!(scheduler.io(0, 2), Some(3));
+    assert_eq!(scheduler.io(0, 1), Some(1));
+    assert_eq!(
+        scheduler.device_list[0].queue,
+        vec![
+            IORequest {
+                pid: 2,
+                remaining: 2
+            },
+            IORequest {
+                pid: 3,
+                remaining: 1
+            }
+        ]
+    );
+
+    // Requests are served one at a time
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.clock, 1);
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().state,
+        PCBState::READY
+    );
+    assert_eq!(
+        scheduler.device_list[0].queue,
+        vec![IORequest {
+            pid: 3,
+            remaining: 1
+        }]
+    );
+    assert_eq!(scheduler.timeout(), Some(3));
+    assert_eq!(scheduler.ready_list, [vec![0], vec![1], vec![3, 2]]);
+
+    assert_eq!(scheduler.io(4, 1), None);
+    assert_eq!(scheduler.io(0, 0), None);
+    scheduler.init();
+    assert_eq!(scheduler.io(0, 1), None);
+}
+
+fn running_pids(scheduler: &Scheduler) -> Vec<usize> {
+    scheduler
+        .cpu_list
+        .iter()
+        .map(|cpu| cpu.running_pid)
+        .collect()
+}
+
+#[test]
+fn multi_core_shared_queue() {
+    let mut scheduler = Scheduler::with_config(Config {
+        cores: 2,
+        ..Config::default()
+    });
+
+    assert_eq!(running_pids(&scheduler), vec![0, 0]);
+    assert_eq!(scheduler.create(1), Some(1)); // Process 1
+    assert_eq!(scheduler.create(1), Some(1)); // Process 2
+    assert_eq!(running_pids(&scheduler), vec![1, 2]);
+
+    // A higher priority process preempts the core running the lowest priority process
+    assert_eq!(scheduler.create(2), Some(1)); // Process 3
+    assert_eq!(running_pids(&scheduler), vec![1, 3]);
+
+    assert_eq!(scheduler.timeout_on(1), Some(1));
+    assert_eq!(running_pids(&scheduler), vec![1, 3]);
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(running_pids(&scheduler), vec![2, 3]);
+    assert_eq!(scheduler.timeout_on(2), None);
+
+    // Commands are issued by the process on the current core
+    assert_eq!(scheduler.switch_cpu(1), Some(3));
+    assert_eq!(scheduler.request(1, 1), Some(3));
+    assert_eq!(
+        scheduler.pcb_list[3].as_ref().unwrap().resources,
+        vec![PCBResource { rid: 1, units: 1 }]
+    );
+    assert_eq!(scheduler.switch_cpu(2), None);
+
+    // An idle core runs process 0
+    assert_eq!(scheduler.switch_cpu(0), Some(2));
+    assert_eq!(scheduler.destroy(2), Some(1));
+    assert_eq!(scheduler.switch_cpu(1), Some(3));
+    assert_eq!(scheduler.destroy(3), Some(0));
+    assert_eq!(running_pids(&scheduler), vec![1, 0]);
+}
+
+#[test]
+fn process_0_takes_turns_in_level_0() {
+    for queue_mode in [QueueMode::Shared, QueueMode::PerCore] {
+        let mut scheduler = Scheduler::with_config(Config {
+            queue_mode,
+            ..Config::default()
+        });
+
+        // Process 0 stays in front of the processes it creates in its level
+        assert_eq!(scheduler.create(0), Some(0));
+        assert_eq!(scheduler.create(0), Some(0));
+        assert_eq!(scheduler.timeout(), Some(1));
+        assert_eq!(scheduler.timeout(), Some(2));
+        assert_eq!(scheduler.timeout(), Some(0));
+        assert_eq!(
+            scheduler.ready_list,
+            [vec![0, 1, 2], Vec::new(), Vec::new()]
+        );
+    }
+}
+
+#[test]
+fn multi_core_affinity() {
+    let mut scheduler = Scheduler::with_config(Config {
+        cores: 2,
+        ..Config::default()
+    });
+
+    let on_core_1 = CreateOptions {
+        affinity: Some(1),
+        ..CreateOptions::default()
+    };
+
+    assert_eq!(scheduler.create_with(2, &on_core_1), Some(0)); // Process 1
+    assert_eq!(scheduler.create_with(2, &on_core_1), Some(0)); // Process 2
+    assert_eq!(running_pids(&scheduler), vec![0, 1]);
+    assert_eq!(scheduler.timeout_on(1), Some(0));
+    assert_eq!(running_pids(&scheduler), vec![0, 2]);
+
+    assert_eq!(
+        scheduler.create_with(
+            1,
+            &CreateOptions {
+                affinity: Some(2),
+                ..CreateOptions::default()
+            }
+        ),
+        None
+    );
+}
+
+#[test]
+fn multi_core_per_core_queues() {
+    let mut scheduler = Scheduler::with_config(Config {
+        cores: 2,
+        queue_mode: QueueMode::PerCore,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1, queued on core 0
+    scheduler.create(1); // Process 2, queued on core 1
+    scheduler.create(2); // Process 3, queued on core 0
+
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().core, Some(0));
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().core, Some(1));
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().core, Some(0));
+    assert_eq!(running_pids(&scheduler), vec![3, 2]);
+    assert_eq!(scheduler.running_pid, 3);
+}
+
+fn unbalanced_per_core_scheduler(balancing: BalanceStrategy) -> Scheduler {
+    let mut scheduler = Scheduler::with_config(Config {
+        cores: 2,
+        queue_mode: QueueMode::PerCore,
+        balancing,
+        ..Config::default()
+    });
+
+    // Process 1 is pinned to core 0 and its children alternate between cores
+    scheduler.create_with(
+        2,
+        &CreateOptions {
+            affinity: Some(0),
+            ..CreateOptions::default()
+        },
+    );
+    for _ in 0..4 {
+        scheduler.create(1);
+    }
+    scheduler.destroy(2);
+
+    scheduler
+}
+
+#[test]
+fn push_migration_balances_queues() {
+    let mut scheduler = unbalanced_per_core_scheduler(BalanceStrategy::PushMigration { period: 1 });
+
+    assert_eq!(scheduler.destroy(4), Some(1));
+    assert_eq!(running_pids(&scheduler), vec![1, 0]);
+
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.pcb_list[5].as_ref().unwrap().core, Some(1));
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().core, Some(0));
+    assert_eq!(running_pids(&scheduler), vec![1, 5]);
+
+    let metrics = scheduler.metrics();
+    assert_eq!(metrics.migrations, 1);
+    assert_eq!(metrics.utilization, vec![1.0, 0.0]);
+}
+
+#[test]
+fn work_stealing_fills_idle_core() {
+    let mut scheduler = unbalanced_per_core_scheduler(BalanceStrategy::WorkStealing);
+
+    assert_eq!(scheduler.destroy(4), Some(1));
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().core, Some(1));
+    assert_eq!(running_pids(&scheduler), vec![1, 3]);
+    assert_eq!(scheduler.metrics().migrations, 1);
+}
+
+#[test]
+fn no_balancing_leaves_core_idle() {
+    let mut scheduler = unbalanced_per_core_scheduler(BalanceStrategy::Disabled);
+
+    assert_eq!(scheduler.destroy(4), Some(1));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(running_pids(&scheduler), vec![1, 0]);
+    assert_eq!(scheduler.metrics().migrations, 0);
+}
+
+#[test]
+fn earliest_deadline_first() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::EarliestDeadlineFirst,
+        ..Config::default()
+    });
+
+    // Full utilization is only admitted under EDF
+    assert_eq!(scheduler.create_periodic(4, 2, None), Some(1));
+    assert_eq!(scheduler.create_periodic(6, 3, None), Some(1));
+
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().state,
+        PCBState::BLOCKED
+    );
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    // Process 1's next job has a later deadline than process 2's current one
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.deadline_misses, Vec::new());
+}
+
+#[test]
+fn rate_monotonic_admission() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::RateMonotonic,
+        ..Config::default()
+    });
+
+    assert_eq!(scheduler.create_periodic(4, 2, None), Some(1));
+    assert_eq!(scheduler.create_periodic(6, 3, None), None);
+    assert_eq!(scheduler.create_periodic(8, 2, None), Some(1));
+
+    // The shorter period wins
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    assert_eq!(scheduler.create_periodic(4, 5, None), None);
+    assert_eq!(scheduler.create_periodic(4, 2, Some(1)), None);
+    assert_eq!(Scheduler::new().create_periodic(4, 2, None), None);
+}
+
+#[test]
+fn deadline_miss_reported() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::EarliestDeadlineFirst,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.request(1, 1);
+    assert_eq!(scheduler.create_periodic(4, 2, None), Some(2));
+
+    // The periodic task blocks behind process 1
+    assert_eq!(scheduler.request(1, 1), Some(1));
+    for _ in 0..4 {
+        scheduler.timeout();
+    }
+
+    assert_eq!(
+        scheduler.deadline_misses,
+        vec![DeadlineMiss {
+            pid: 2,
+            deadline: 4
+        }]
+    );
+    assert_eq!(scheduler.metrics().deadline_misses, 1);
+
+    // The new job runs once the resource is free
+    assert_eq!(scheduler.release(1, 1), Some(2));
+    assert_eq!(
+        scheduler.pcb_list[2]
+            .as_ref()
+            .unwrap()
+            .task
+            .unwrap()
+            .release,
+        4
+    );
+}
+
+#[test]
+fn completely_fair_weights_by_priority() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::CompletelyFair,
+        ..Config::default()
+    });
+
+    assert_eq!(scheduler.create(1), Some(1)); // Process 1
+    assert_eq!(scheduler.create(2), Some(1)); // Process 2
+    assert_eq!(
+        scheduler.cfs_queue.iter().copied().collect::<Vec<_>>(),
+        vec![(0, 1), (0, 2)]
+    );
+
+    // Process 2 has twice the weight, so it runs for twice as long
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().vruntime, 2);
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().vruntime, 2);
+    assert_eq!(scheduler.min_vruntime, 2);
+}
+
+#[test]
+fn completely_fair_charges_every_core() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::CompletelyFair,
+        cores: 2,
+        ..Config::default()
+    });
+
+    for _ in 0..3 {
+        scheduler.create(1);
+    }
+    assert_eq!(
+        (scheduler.running(0), scheduler.running(1)),
+        (Some(1), Some(2))
+    );
+
+    // A tick is charged to the process on each core, not only the one timing out
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().vruntime, 2);
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().vruntime, 2);
+    assert_eq!(scheduler.running(1), Some(3));
+
+    for _ in 0..5 {
+        scheduler.timeout();
+    }
+    for pid in 1..=3 {
+        assert_eq!(scheduler.pcb_list[pid].as_ref().unwrap().vruntime, 8);
+    }
+}
+
+#[test]
+fn completely_fair_woken_placement() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::CompletelyFair,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.create(2); // Process 2
+    for _ in 0..3 {
+        scheduler.timeout();
+    }
+
+    assert_eq!(scheduler.io(0, 3), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(1));
+
+    // The sleeper is moved up to just behind the minimum vruntime
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().vruntime, 3);
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().vruntime, 5);
+
+    // New processes start at the minimum vruntime
+    assert_eq!(scheduler.create(0), Some(1));
+    assert_eq!(scheduler.pcb_list[3].as_ref().unwrap().vruntime, 5);
+}
+
+#[test]
+fn shortest_job_first() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::ShortestJobFirst,
+        ..Config::default()
+    });
+
+    let burst = |burst| CreateOptions {
+        burst: Some(burst),
+        ..CreateOptions::default()
+    };
+
+    assert_eq!(scheduler.create_with(1, &burst(8)), Some(1));
+
+    // A shorter job waits for the running process to give up the CPU
+    assert_eq!(scheduler.create_with(1, &burst(2)), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(scheduler.io(0, 1), Some(2));
+
+    // The next estimate averages the 1 tick burst with the 8 tick estimate
+    assert_eq!(
+        scheduler.pcb_list[1].as_ref().unwrap().burst.estimate,
+        Some(4)
+    );
+
+    // Process 1 beats process 3 and its default estimate once process 2 blocks
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.io(0, 1), Some(1));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().burst.estimate,
+        Some(1)
+    );
+
+    assert_eq!(
+        scheduler.ps(),
+        "PID PPID GRP PRI STATE             EST RUN NAME\n  \
+           0    -   0   0 READY               -   0 -\n  \
+           1    0   0   1 READY               4   0 -\n  \
+           2    1   0   1 BLOCKED             1   0 -\n  \
+           3    1   0   1 READY               5   0 -\n"
+    );
+}
+
+#[test]
+fn shortest_remaining_time_first() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::ShortestRemainingTimeFirst,
+        ..Config::default()
+    });
+
+    let burst = |burst| CreateOptions {
+        burst: Some(burst),
+        ..CreateOptions::default()
+    };
+
+    assert_eq!(scheduler.create_with(1, &burst(8)), Some(1));
+
+    // A shorter job preempts the running process
+    assert_eq!(scheduler.create_with(1, &burst(2)), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    // Ties go to the running process
+    assert_eq!(scheduler.create_with(1, &burst(1)), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    // An overrun leaves no time remaining until the burst ends
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().burst.elapsed, 3);
+    assert_eq!(scheduler.io(0, 1), Some(3));
+    assert_eq!(
+        scheduler.pcb_list[2].as_ref().unwrap().burst.estimate,
+        Some(2)
+    );
+}
+
+#[test]
+fn shortest_remaining_time_charges_every_core() {
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::ShortestRemainingTimeFirst,
+        cores: 2,
+        ..Config::default()
+    });
+
+    scheduler.create(1); // Process 1
+    scheduler.create(1); // Process 2
+    for _ in 0..4 {
+        assert_eq!(scheduler.timeout(), Some(1));
+    }
+
+    // Process 2 ran on core 1 for every tick as well
+    assert_eq!(scheduler.running(1), Some(2));
+    assert_eq!(scheduler.pcb_list[1].as_ref().unwrap().burst.elapsed, 4);
+    assert_eq!(scheduler.pcb_list[2].as_ref().unwrap().burst.elapsed, 4);
+}
+
+#[test]
+fn configurable_process_table() {
+    let mut scheduler = Scheduler::with_config(Config {
+        processes: 64,
+        ..Config::default()
+    });
+
+    for _ in 1..64 {
+        assert_ne!(scheduler.create(0), None);
+    }
+    assert_eq!(scheduler.next_pid(), None);
+    assert_eq!(scheduler.create(0), None);
+
+    // Freed slots are reused lowest first
+    assert_eq!(scheduler.destroy(40), Some(0));
+    assert_eq!(scheduler.destroy(7), Some(0));
+    assert_eq!(scheduler.next_pid(), Some(7));
+    assert_eq!(scheduler.create(2), Some(7));
+    assert_eq!(scheduler.next_pid(), Some(40));
+
+    // Resetting keeps the configured size
+    scheduler.init();
+    assert_eq!(scheduler.pcb_list.len(), 64);
+    assert_eq!(scheduler.next_pid(), Some(1));
+}
+
+#[test]
+fn destroy_deep_chain() {
+    let mut scheduler = Scheduler::with_config(Config {
+        processes: 5000,
+        ..Config::default()
+    });
+
+    // Process 1 holds resource 0 and every descendant blocks on it after creating its child
+    scheduler.create(1);
+    scheduler.request(0, 1);
+    scheduler.create(2);
+    for _ in 2..4999 {
+        scheduler.create(2);
+        scheduler.request(0, 1);
+    }
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    assert_eq!(scheduler.rcb_list[0].waitlist.len(), 4998);
+
+    let destroyed = scheduler.destroy_tree(2).unwrap();
+    assert_eq!(destroyed, (2..5000).collect::<Vec<_>>());
+    assert_eq!(scheduler.running_pid, 1);
+    assert!(scheduler.rcb_list[0].waitlist.is_empty());
+    assert!(scheduler.pcb_list[1].as_ref().unwrap().children.is_empty());
+    assert_eq!(scheduler.next_pid(), Some(2));
+}
+
+#[test]
+fn destroy_tree_reschedules_once() {
+    let mut scheduler = Scheduler::new();
+
+    scheduler.create(1); // Process 1
+    scheduler.create(1); // Process 2
+    scheduler.timeout();
+    scheduler.request(3, 1);
+    scheduler.timeout();
+    scheduler.create(0); // Process 3
+    scheduler.create(2); // Process 4
+    scheduler.request(3, 1);
+    scheduler.request(3, 2);
+
+    // Releasing process 2's unit wakes process 4 mid-teardown, which must not stop
+    // process 1's remaining children from being destroyed
+    assert_eq!(scheduler.destroy_tree(1), Some(vec![1, 2, 3, 4]));
+    assert_eq!(scheduler.running_pid, 0);
+    assert_eq!(scheduler.pcb_list, pcb_list_default());
+    assert_eq!(scheduler.rcb_list[3].units_available, 3);
+
+    // Without preemption, a reschedule per released resource would hand the CPU to
+    // the first process woken instead of the shortest job
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::ShortestJobFirst,
+        ..Config::default()
+    });
+    let burst = |burst| CreateOptions {
+        burst: Some(burst),
+        ..CreateOptions::default()
+    };
+
+    scheduler.create_with(1, &burst(1)); // Process 1
+    scheduler.create_with(1, &burst(2)); // Process 2
+    scheduler.create_with(1, &burst(5)); // Process 3
+    scheduler.create_with(1, &burst(3)); // Process 4
+    assert_eq!(scheduler.io(0, 1), Some(2));
+    scheduler.request(0, 1);
+    scheduler.request(2, 2);
+    assert_eq!(scheduler.io(0, 1), Some(4));
+    assert_eq!(scheduler.request(2, 1), Some(3));
+    assert_eq!(scheduler.request(0, 1), Some(0));
+
+    assert_eq!(scheduler.destroy_tree(2), Some(vec![2]));
+    assert_eq!(scheduler.running_pid, 4);
+}
+
+#[test]
+fn named_processes_and_resources() {
+    let mut scheduler = Scheduler::with_config(Config {
+        resources: ResourceConfig::parse_list("disk=2, printer=1, 3").unwrap(),
+        ..Config::default()
+    });
+    assert_eq!(scheduler.rcb_list.len(), 3);
+    assert_eq!(scheduler.rid_of("printer"), Some(1));
+    assert_eq!(scheduler.rcb_list[2].name, None);
+    assert_eq!(scheduler.rcb_list[2].inventory, 3);
+
+    let named = |name: &str| CreateOptions {
+        name: Some(String::from(name)),
+        ..CreateOptions::default()
+    };
+    assert_eq!(scheduler.create_with(1, &named("logger")), Some(1));
+    assert_eq!(scheduler.pid_of("logger"), Some(1));
+
+    // Names are unique among live processes and can't look like PIDs
+    assert_eq!(scheduler.create_with(1, &named("logger")), None);
+    assert_eq!(scheduler.create_with(1, &named("7")), None);
+
+    // A destroyed process gives its name up
+    assert_eq!(scheduler.create_with(2, &named("worker")), Some(2));
+    assert_eq!(scheduler.destroy(2), Some(1));
+    assert_eq!(scheduler.pid_of("worker"), None);
+    assert_eq!(scheduler.create_with(1, &named("worker")), Some(1));
+    assert_eq!(scheduler.pid_of("worker"), Some(2));
+
+    assert_eq!(
+        scheduler.ps(),
+        "PID PPID GRP PRI STATE             EST RUN NAME\n  \
+           0    -   0   0 READY               -   0 -\n  \
+           1    0   0   1 READY               -   0 logger\n  \
+           2    1   0   1 READY               -   0 worker\n"
+    );
+
+    assert_eq!(
+        ResourceConfig::parse_list("disk=2, disk=1"),
+        Err(String::from("Duplicate resource name disk"))
+    );
+    assert!(ResourceConfig::parse_list("disk=two").is_err());
+    assert!(ResourceConfig::parse_list("3=1").is_err());
+}
+
+#[test]
+fn query_api() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(1, 1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.request(1, 1), Some(1));
+    assert_eq!(scheduler.create(2), Some(3));
+    assert_eq!(scheduler.create(2), Some(3));
+    assert_eq!(scheduler.lock(0), Some(3));
+    assert_eq!(scheduler.timeout(), Some(4));
+    assert_eq!(scheduler.lock(0), Some(3));
+
+    assert_eq!(scheduler.process(3).map(|pcb| pcb.priority), Some(2));
+    assert_eq!(scheduler.process(5), None);
+    assert_eq!(scheduler.process(99), None);
+    assert_eq!(scheduler.processes().flatten().count(), 5);
+    assert_eq!(scheduler.processes().position(|pcb| pcb.is_none()), Some(5));
+    assert_eq!(scheduler.cores(), 1);
+    assert_eq!(scheduler.running(0), Some(3));
+    assert_eq!(scheduler.running(1), None);
+    assert_eq!(scheduler.children(0), Some(&[1][..]));
+    assert_eq!(scheduler.children(1), Some(&[2, 3][..]));
+    assert_eq!(scheduler.children(5), None);
+    assert!(scheduler.descendants(0).eq([1, 2, 3, 4]));
+    assert!(scheduler.descendants(3).eq([4]));
+    assert!(scheduler.descendants(5).eq([]));
+
+    assert_eq!(
+        scheduler.resource(1).map(|rcb| rcb.units_available),
+        Some(0)
+    );
+    assert_eq!(scheduler.resource(4), None);
+    assert!(scheduler
+        .resources()
+        .map(|rcb| rcb.units_available)
+        .eq([1, 0, 2, 3]));
+    assert!(scheduler.holders(1).eq([(1, 1)]));
+    assert!(scheduler.holders(2).eq([]));
+    assert_eq!(
+        scheduler.waiters(1),
+        Some(&VecDeque::from([RCBResource {
+            pid: 2,
+            units: 1,
+            expires: None
+        }]))
+    );
+    assert_eq!(scheduler.waiters(4), None);
+
+    assert!(scheduler.ready_queue(1).unwrap().eq([1]));
+    assert!(scheduler.ready_queue(2).unwrap().eq([3]));
+    assert!(scheduler.ready_queue(3).is_none());
+
+    assert_eq!(
+        scheduler.blocked_on(2),
+        Some(BlockedOn::Resource { rid: 1, units: 1 })
+    );
+    assert_eq!(scheduler.blocked_on(4), Some(BlockedOn::Mutex(0)));
+    assert_eq!(scheduler.blocked_on(1), None);
+    assert_eq!(scheduler.blocked_on(5), None);
+}
+
+#[test]
+fn dry_run_leaves_state_untouched() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(1, 1), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    let request = Command::Request {
+        rid: 1,
+        units: 1,
+        timeout: None,
+    };
+    let table = scheduler.ps();
+    assert_eq!(
+        scheduler.dry_run(&request),
+        DryRun {
+            running: Some(1),
+            blocked: vec![2],
+            ..DryRun::default()
+        }
+    );
+    assert_eq!(scheduler.ps(), table);
+    assert_eq!(scheduler.running_pid, 2);
+    assert_eq!(scheduler.ready_list, [vec![0], vec![2, 1], Vec::new()]);
+
+    // Rejected commands report no running process, only why they were rejected
+    assert_eq!(
+        scheduler.dry_run(&Command::Destroy(5)),
+        DryRun {
+            refusal: Some(Refusal::UnknownId),
+            ..DryRun::default()
+        }
+    );
+
+    assert_eq!(scheduler.apply(&request), Some(1));
+    assert_eq!(
+        scheduler.dry_run(&Command::Release { rid: 1, units: 1 }),
+        DryRun {
+            running: Some(1),
+            woken: vec![2],
+            ..DryRun::default()
+        }
+    );
+    assert_eq!(
+        scheduler.dry_run(&Command::Destroy(1)),
+        DryRun {
+            running: Some(0),
+            destroyed: vec![1, 2],
+            ..DryRun::default()
+        }
+    );
+    assert_eq!(scheduler.rcb_list[1].waitlist.len(), 1);
+}
+
+#[test]
+fn request_timeout() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(1, 1), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+
+    // tryrq fails without blocking
+    assert_eq!(scheduler.try_request(1, 1), None);
+    assert_eq!(scheduler.running_pid, 2);
+    assert!(scheduler.rcb_list[1].waitlist.is_empty());
+
+    assert_eq!(scheduler.request_within(1, 1, Some(2)), Some(1));
+    assert_eq!(scheduler.clock, 1);
+    assert_eq!(scheduler.timers, BTreeSet::from([(3, 2)]));
+    assert_eq!(scheduler.timeout(), Some(1));
+    assert_eq!(
+        scheduler.blocked_on(2),
+        Some(BlockedOn::Resource { rid: 1, units: 1 })
+    );
+
+    // The request gives up on the second tick
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.process(2).unwrap().state, PCBState::READY);
+    assert_eq!(scheduler.process(2).unwrap().failed_request, Some(1));
+    assert!(scheduler.rcb_list[1].waitlist.is_empty());
+    assert!(scheduler.timers.is_empty());
+
+    // A granted request cancels its timer
+    assert_eq!(scheduler.request_within(1, 1, Some(5)), Some(1));
+    assert_eq!(scheduler.process(2).unwrap().failed_request, None);
+    assert_eq!(scheduler.release(1, 1), Some(1));
+    assert!(scheduler.timers.is_empty());
+    assert!(scheduler.holders(1).eq([(2, 1)]));
+
+    // So does destroying the waiting process
+    assert_eq!(scheduler.create(2), Some(3));
+    assert_eq!(scheduler.request_within(1, 1, Some(5)), Some(1));
+    assert_eq!(scheduler.timers.len(), 1);
+    assert_eq!(scheduler.destroy(3), Some(1));
+    assert!(scheduler.timers.is_empty());
+}
+
+/// Process 1 holds R0 and waits for R1, which its child holds while waiting for R0
+fn deadlocked_scheduler(config: Config, child_priority: i32) -> Scheduler {
+    let mut scheduler = Scheduler::with_config(config);
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    scheduler.create(child_priority);
+    if scheduler.running_pid == 1 {
+        scheduler.timeout();
+    }
+    assert_eq!(scheduler.request(1, 1), Some(2));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    scheduler.request(1, 1);
+
+    scheduler
+}
+
+#[test]
+fn deadlock_detection() {
+    let scheduler = deadlocked_scheduler(Config::default(), 1);
+    assert_eq!(scheduler.running_pid, 0);
+    assert_eq!(scheduler.deadlocked(), vec![1, 2]);
+
+    // A waiter that can be served once another process finishes isn't deadlocked
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    assert_eq!(scheduler.create(2), Some(2));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    assert!(scheduler.deadlocked().is_empty());
+    assert!(scheduler
+        .recover(RecoveryStrategy::Terminate(VictimPolicy::LowestPriority))
+        .is_empty());
+    assert_eq!(scheduler.running_pid, 1);
+}
+
+#[test]
+fn deadlock_recovery_by_rollback() {
+    let mut scheduler = deadlocked_scheduler(Config::default(), 1);
+
+    // Both have the same priority, so the higher PID is the victim
+    let actions = scheduler.recover(RecoveryStrategy::Rollback(VictimPolicy::LowestPriority));
+    assert_eq!(
+        actions,
+        vec![RecoveryAction::RolledBack {
+            pid: 2,
+            released: vec![PCBResource { rid: 1, units: 1 }]
+        }]
+    );
+    assert_eq!(scheduler.recovery_log, actions);
+    assert_eq!(scheduler.running_pid, 1);
+    assert!(scheduler.deadlocked().is_empty());
+
+    // The victim waits for its request again
+    assert!(scheduler.holders(1).eq([(1, 1)]));
+    assert_eq!(
+        scheduler.blocked_on(2),
+        Some(BlockedOn::Resource { rid: 0, units: 1 })
+    );
+    assert_eq!(scheduler.release(0, 1), Some(1));
+    assert!(scheduler.holders(0).eq([(2, 1)]));
+}
+
+#[test]
+fn deadlock_recovery_by_termination() {
+    let mut scheduler = deadlocked_scheduler(Config::default(), 2);
+    assert_eq!(
+        scheduler.recover(RecoveryStrategy::Terminate(VictimPolicy::Youngest)),
+        vec![RecoveryAction::Terminated {
+            pid: 2,
+            destroyed: vec![2]
+        }]
+    );
+    assert_eq!(scheduler.running_pid, 1);
+    assert!(scheduler.holders(1).eq([(1, 1)]));
+
+    // Process 1 has the lower priority and takes its child down with it
+    let mut scheduler = deadlocked_scheduler(Config::default(), 2);
+    assert_eq!(
+        scheduler.recover(RecoveryStrategy::Terminate(VictimPolicy::LowestPriority)),
+        vec![RecoveryAction::Terminated {
+            pid: 1,
+            destroyed: vec![1, 2]
+        }]
+    );
+    assert_eq!(scheduler.running_pid, 0);
+    assert_eq!(scheduler.rcb_list, rcb_list_default());
+
+    // Process 1 holds fewer units than its child, which also has two of R3
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.request(1, 1), Some(2));
+    assert_eq!(scheduler.request(3, 2), Some(2));
+    assert_eq!(scheduler.request(0, 1), Some(1));
+    assert_eq!(scheduler.request(1, 1), Some(0));
+    assert_eq!(
+        scheduler.recover(RecoveryStrategy::Terminate(VictimPolicy::FewestUnits)),
+        vec![RecoveryAction::Terminated {
+            pid: 1,
+            destroyed: vec![1, 2]
+        }]
+    );
+}
+
+#[test]
+fn automatic_deadlock_recovery() {
+    let scheduler = deadlocked_scheduler(
+        Config {
+            deadlock_recovery: Some(RecoveryStrategy::Rollback(VictimPolicy::Youngest)),
+            ..Config::default()
+        },
+        1,
+    );
+
+    // The request that closed the cycle is granted by rolling back process 2
+    assert_eq!(scheduler.running_pid, 1);
+    assert_eq!(scheduler.recovery_log.len(), 1);
+    assert!(scheduler.deadlocked().is_empty());
+}
+
+#[test]
+fn dynamic_resources() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.make_resource(2, Some("disk")), Some(0));
+    assert_eq!(scheduler.rcb_list.len(), 5);
+    assert_eq!(scheduler.rid_of("disk"), Some(4));
+    assert_eq!(scheduler.make_resource(1, Some("disk")), None);
+    assert_eq!(scheduler.make_resource(-1, None), None);
+
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.request(4, 2), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.request(4, 1), Some(1));
+
+    // New units go to the waiters
+    assert_eq!(scheduler.add_units(4, 1), Some(1));
+    assert_eq!(scheduler.rcb_list[4].inventory, 3);
+    assert!(scheduler.holders(4).eq([(1, 2), (2, 1)]));
+    assert_eq!(scheduler.process(2).unwrap().state, PCBState::READY);
+
+    // Held units are only taken back when preempting, youngest first on equal priorities
+    assert_eq!(scheduler.remove_units(4, 1, false), None);
+    assert_eq!(scheduler.remove_units(4, 4, true), None);
+    assert_eq!(scheduler.remove_units(4, 2, true), Some(1));
+    assert!(scheduler.holders(4).eq([(1, 1)]));
+    assert_eq!(scheduler.rcb_list[4].inventory, 1);
+    assert_eq!(scheduler.rcb_list[4].units_available, 0);
+
+    // A request that can no longer fit fails instead of waiting forever
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.request(4, 1), Some(1));
+    assert_eq!(scheduler.remove_units(4, 1, true), Some(1));
+    assert_eq!(scheduler.rcb_list[4].inventory, 0);
+    assert!(scheduler.rcb_list[4].waitlist.is_empty());
+    assert!(scheduler.holders(4).eq([]));
+    assert_eq!(scheduler.process(2).unwrap().state, PCBState::READY);
+    assert_eq!(scheduler.process(2).unwrap().failed_request, Some(4));
+
+    // Runtime resources are gone after a reset
+    scheduler.init();
+    assert_eq!(scheduler.rcb_list, rcb_list_default());
+}
+
+#[test]
+fn process_groups() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.set_group(1, 1), Some(1));
+    assert_eq!(scheduler.set_group(0, 1), None);
+
+    // Children join the group of their parent
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.create(1), Some(1));
+    assert!(scheduler.group_members(1).eq([1, 2, 3]));
+    assert_eq!(scheduler.set_group(3, 2), Some(1));
+    assert!(scheduler.group_members(1).eq([1, 2]));
+    assert!(scheduler.group_members(0).eq([0]));
+
+    // Every member has to be a descendant of the running process
+    assert_eq!(scheduler.timeout(), Some(2));
+    assert_eq!(scheduler.suspend_group(2), None);
+    assert_eq!(scheduler.suspend_group(1), None);
+    assert_eq!(scheduler.suspend_group(0), None);
+    assert_eq!(scheduler.suspend_group(5), None);
+    assert_eq!(scheduler.timeout(), Some(3));
+    assert_eq!(scheduler.timeout(), Some(1));
+
+    assert_eq!(scheduler.suspend_group(2), Some(1));
+    assert_eq!(
+        scheduler.process(3).unwrap().state,
+        PCBState::SUSPENDED_READY
+    );
+    assert!(scheduler.ready_queue(1).unwrap().eq([1, 2]));
+    assert_eq!(scheduler.resume_group(2), Some(1));
+    assert_eq!(scheduler.process(3).unwrap().state, PCBState::READY);
+
+    // Raising the priority of the group preempts the running process
+    assert_eq!(scheduler.set_group_priority(2, 3), None);
+    assert_eq!(scheduler.set_group_priority(2, 2), Some(3));
+    assert_eq!(scheduler.destroy_group(1), None);
+    assert_eq!(scheduler.set_group_priority(2, 1), Some(1));
+    assert!(scheduler.ready_queue(1).unwrap().eq([1, 2, 3]));
+    assert!(scheduler.ps().contains("  3    1   2   1 READY"));
+
+    assert_eq!(scheduler.destroy_group(2), Some(1));
+    assert!(scheduler.process(3).is_none());
+    assert!(scheduler.group_members(1).eq([1, 2]));
+
+    // Destroying a group of the running process moves on to the next one
+    assert_eq!(scheduler.destroy_group(1), Some(0));
+    assert!(scheduler.group_members(1).eq([]));
+
+    scheduler.init();
+    assert!(scheduler.group_members(0).eq([0]));
+
+    // Periodic tasks join the group of their parent too
+    let mut scheduler = Scheduler::with_config(Config {
+        policy: SchedulingPolicy::EarliestDeadlineFirst,
+        ..Config::default()
+    });
+    assert_eq!(scheduler.create(1), Some(1));
+    assert_eq!(scheduler.set_group(1, 1), Some(1));
+    assert_eq!(scheduler.create_periodic(4, 2, None), Some(2));
+    assert!(scheduler.group_members(1).eq([1, 2]));
+}
+
+#[test]
+fn config_names_parse() {
+    assert_eq!(
+        SchedulingPolicy::parse("edf"),
+        Some(SchedulingPolicy::EarliestDeadlineFirst)
+    );
+    assert_eq!(
+        SchedulingPolicy::parse("srtf"),
+        Some(SchedulingPolicy::ShortestRemainingTimeFirst)
+    );
+    assert_eq!(SchedulingPolicy::parse("fifo"), None);
+    assert_eq!(QueueMode::parse("per-core"), Some(QueueMode::PerCore));
+    assert_eq!(
+        ExitPolicy::parse("reparent-to-init"),
+        Some(ExitPolicy::ReparentToInit)
+    );
+
+    // Push migration runs every tick unless a period is given
+    assert_eq!(
+        BalanceStrategy::parse("push"),
+        Some(BalanceStrategy::PushMigration { period: 1 })
+    );
+    assert_eq!(
+        BalanceStrategy::parse("push:4"),
+        Some(BalanceStrategy::PushMigration { period: 4 })
+    );
+    assert_eq!(BalanceStrategy::parse("stealing:4"), None);
+    assert_eq!(BalanceStrategy::parse("push:often"), None);
+}
+
+#[test]
+fn refusal_reasons() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.create(1), Some(1));
+
+    assert_eq!(scheduler.apply(&Command::Destroy(9)), None);
+    assert_eq!(scheduler.last_refusal(), Some(Refusal::UnknownId));
+    assert_eq!(scheduler.apply(&Command::Destroy(0)), None);
+    assert_eq!(scheduler.last_refusal(), Some(Refusal::NotPermitted));
+    assert_eq!(
+        scheduler.apply(&Command::Request {
+            rid: 1,
+            units: 0,
+            timeout: None
+        }),
+        None
+    );
+    assert_eq!(scheduler.last_refusal(), Some(Refusal::InvalidArgument));
+    assert_eq!(scheduler.apply(&Command::Resume(1)), None);
+    assert_eq!(scheduler.last_refusal(), Some(Refusal::InvalidState));
+
+    // A nonexistent process is reported as such, not as someone else's child
+    assert_eq!(scheduler.apply(&Command::Suspend(5)), None);
+    assert_eq!(scheduler.last_refusal(), Some(Refusal::UnknownId));
+
+    // The next command starts without a reason, as does a refusal that gives none
+    assert_eq!(scheduler.apply(&Command::Timeout), Some(1));
+    assert_eq!(scheduler.last_refusal(), None);
+    assert_eq!(scheduler.apply(&Command::Destroy(-1)), None);
+    assert_eq!(scheduler.last_refusal(), None);
+
+    // A preview keeps the reason too
+    assert_eq!(
+        scheduler.dry_run(&Command::Destroy(9)).refusal,
+        Some(Refusal::UnknownId)
+    );
+}